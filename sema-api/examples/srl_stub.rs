@@ -0,0 +1,34 @@
+// A stand-in for the allennlp srl service, for trying out `srl_cross_check` without the real model.
+// Serves canned responses from a json file of { "<sentence>": <SRLResponse> }.
+//
+//   SRL_STUB_FIXTURES=examples/srl_stub_fixtures.json SRL_STUB_PORT=8099 cargo run --example srl_stub
+//
+// Then point the api at it with `srl_url: "http://localhost:8099/predict"` and `srl_cross_check: true`.
+use std::env;
+
+use actix_web::{App, HttpServer};
+
+#[path = "../tests/common/srl_stub.rs"]
+mod srl_stub;
+
+use srl_stub::{load_fixtures, predict};
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+  let fixtures_path =
+    env::var("SRL_STUB_FIXTURES").unwrap_or_else(|_| "examples/srl_stub_fixtures.json".to_string());
+  let port = env::var("SRL_STUB_PORT").unwrap_or_else(|_| "8099".to_string());
+
+  let fixtures = load_fixtures(&fixtures_path)?;
+
+  println!("srl stub listening on port {} with {} sentences", port, fixtures.len());
+
+  HttpServer::new(move || {
+    App::new()
+      .app_data(fixtures.clone())
+      .service(predict)
+  })
+  .bind(format!("0.0.0.0:{}", port))?
+  .run()
+  .await
+}
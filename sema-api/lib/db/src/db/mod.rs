@@ -1 +1,2 @@
+pub mod parse_result;
 pub mod verb_frame;
\ No newline at end of file
@@ -0,0 +1,165 @@
+use uuid::Uuid;
+use serde_derive::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgExecutor};
+use anyhow::Result;
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct DBParseResult {
+  pub id: Uuid,
+
+  pub subject: String,
+
+  pub input_text: Vec<String>,
+
+  pub request: serde_json::Value, // the original text-to-json request, used for re-runs
+
+  pub parts_summary: serde_json::Value,
+
+  pub sema_sentences: serde_json::Value,
+
+  pub engine_version: String,
+
+  pub schema_version: String,
+
+  pub duration_ms: i64,
+
+  pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewParseResult {
+  pub subject: String,
+
+  pub input_text: Vec<String>,
+
+  pub request: serde_json::Value,
+
+  pub parts_summary: serde_json::Value,
+
+  pub sema_sentences: serde_json::Value,
+
+  pub engine_version: String,
+
+  pub schema_version: String,
+
+  pub duration_ms: i64,
+}
+
+pub async fn insert_parse_result(
+  pool: impl PgExecutor<'_>,
+  new_parse_result: NewParseResult
+) -> Result<DBParseResult> {
+  let parse_result = sqlx::query_as::<_, DBParseResult>(
+    r#"
+      INSERT INTO parse_result (
+        subject,
+        input_text,
+        request,
+        parts_summary,
+        sema_sentences,
+        engine_version,
+        schema_version,
+        duration_ms
+      )
+      VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+      RETURNING
+        id,
+        subject,
+        input_text,
+        request,
+        parts_summary,
+        sema_sentences,
+        engine_version,
+        schema_version,
+        duration_ms,
+        created_at
+    "#
+  )
+  .bind(new_parse_result.subject)
+  .bind(new_parse_result.input_text)
+  .bind(new_parse_result.request)
+  .bind(new_parse_result.parts_summary)
+  .bind(new_parse_result.sema_sentences)
+  .bind(new_parse_result.engine_version)
+  .bind(new_parse_result.schema_version)
+  .bind(new_parse_result.duration_ms)
+  .fetch_one(pool)
+  .await?;
+
+  Ok(parse_result)
+}
+
+pub async fn get_parse_result_by_id(
+  pool: impl PgExecutor<'_>,
+  id: Uuid
+) -> Result<Option<DBParseResult>> {
+  let parse_result = sqlx::query_as::<_, DBParseResult>(
+    r#"
+      SELECT
+        id,
+        subject,
+        input_text,
+        request,
+        parts_summary,
+        sema_sentences,
+        engine_version,
+        schema_version,
+        duration_ms,
+        created_at
+      FROM
+        parse_result
+      WHERE
+        id = $1
+    "#
+  )
+  .bind(id)
+  .fetch_optional(pool)
+  .await?;
+
+  Ok(parse_result)
+}
+
+// Newest first. `after`/`before` bound the time range, `subject` filters to a single caller.
+pub async fn list_parse_results(
+  pool: impl PgExecutor<'_>,
+  subject: Option<&str>,
+  after: Option<DateTime<Utc>>,
+  before: Option<DateTime<Utc>>,
+  limit: i64
+) -> Result<Vec<DBParseResult>> {
+  let parse_results = sqlx::query_as::<_, DBParseResult>(
+    r#"
+      SELECT
+        id,
+        subject,
+        input_text,
+        request,
+        parts_summary,
+        sema_sentences,
+        engine_version,
+        schema_version,
+        duration_ms,
+        created_at
+      FROM
+        parse_result
+      WHERE
+        ($1::TEXT IS NULL OR subject = $1)
+        AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+        AND ($3::TIMESTAMPTZ IS NULL OR created_at < $3)
+      ORDER BY
+        created_at DESC
+      LIMIT $4
+    "#
+  )
+  .bind(subject)
+  .bind(after)
+  .bind(before)
+  .bind(limit)
+  .fetch_all(pool)
+  .await?;
+
+  Ok(parse_results)
+}
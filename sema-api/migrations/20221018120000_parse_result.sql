@@ -0,0 +1,15 @@
+CREATE TABLE parse_result (
+  id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+  subject TEXT NOT NULL,
+  input_text TEXT[] NOT NULL,
+  request JSONB NOT NULL,
+  parts_summary JSONB NOT NULL,
+  sema_sentences JSONB NOT NULL,
+  engine_version TEXT NOT NULL,
+  schema_version TEXT NOT NULL,
+  duration_ms BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX parse_result_created_at_idx ON parse_result (created_at DESC);
+CREATE INDEX parse_result_subject_created_at_idx ON parse_result (subject, created_at DESC);
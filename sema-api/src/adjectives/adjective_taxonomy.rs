@@ -0,0 +1,81 @@
+use anyhow::Result;
+use std::fs::File;
+
+use once_cell::sync::OnceCell;
+
+// Adjectives that aren't in any category.
+pub const OTHER_CATEGORY: &str = "other";
+
+// e.g. "color", with "red" and "blue", amplified by "dark" and "light".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjectiveCategory {
+  pub name: String,
+
+  pub adjectives: Vec<String>,
+
+  #[serde(default)]
+  pub amplifiers: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdjectiveTaxonomy {
+  pub categories: Vec<AdjectiveCategory>,
+}
+
+impl AdjectiveTaxonomy {
+  pub fn new(data_path: &str) -> Result<Self> {
+    let path = format!("{}/adjective_taxonomy.ron", data_path);
+    let file = File::open(&path)?;
+    let adjective_taxonomy: AdjectiveTaxonomy = ron::de::from_reader(file)?;
+
+    Ok(adjective_taxonomy)
+  }
+
+  pub fn init(data_path: &str) {
+    let adjective_taxonomy =
+      AdjectiveTaxonomy::new(data_path).expect("Unable to create AdjectiveTaxonomy instance");
+    ADJECTIVE_TAXONOMY
+      .set(adjective_taxonomy)
+      .expect("Unable to set ADJECTIVE_TAXONOMY");
+  }
+
+  pub fn get_category(adjective: &str) -> Option<&'static AdjectiveCategory> {
+    let adjective_taxonomy = ADJECTIVE_TAXONOMY
+      .get()
+      .expect("ADJECTIVE_TAXONOMY is not initialized");
+
+    adjective_taxonomy
+      .categories
+      .iter()
+      .find(|category| {
+        category
+          .adjectives
+          .iter()
+          .any(|a| a.eq_ignore_ascii_case(adjective))
+      })
+  }
+
+  pub fn category_name(adjective: &str) -> String {
+    AdjectiveTaxonomy::get_category(adjective)
+      .map(|category| category.name.clone())
+      .unwrap_or_else(|| OTHER_CATEGORY.to_string())
+  }
+
+  pub fn contains(adjective: &str) -> bool {
+    AdjectiveTaxonomy::get_category(adjective).is_some()
+  }
+
+  // "dark" in "dark red"
+  pub fn amplifies(amplifier: &str, adjective: &str) -> bool {
+    AdjectiveTaxonomy::get_category(adjective)
+      .map(|category| {
+        category
+          .amplifiers
+          .iter()
+          .any(|a| a.eq_ignore_ascii_case(amplifier))
+      })
+      .unwrap_or(false)
+  }
+}
+
+pub static ADJECTIVE_TAXONOMY: OnceCell<AdjectiveTaxonomy> = OnceCell::new();
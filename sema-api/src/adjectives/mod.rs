@@ -0,0 +1,7 @@
+pub mod adjective_taxonomy;
+
+use self::adjective_taxonomy::AdjectiveTaxonomy;
+
+pub fn init_adjectives_cells(data_path: &str) {
+  AdjectiveTaxonomy::init(data_path);
+}
@@ -0,0 +1,10 @@
+// Build time information, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/built.rs"));
+
+// e.g. "0.1.0 (0.4.1-10-gca2af4f)". Stored with parse results so regressions can be tied to a build.
+pub fn engine_version() -> String {
+  match GIT_VERSION {
+    Some(git_version) => format!("{} ({})", PKG_VERSION, git_version),
+    None => PKG_VERSION.to_string(),
+  }
+}
@@ -1,6 +1,6 @@
 use once_cell::sync::OnceCell;
 use serde::Deserialize;
-use std::{env, fs::File};
+use std::{collections::HashMap, env, fs::File};
 
 pub static CONFIG: OnceCell<Config> = OnceCell::new();
 
@@ -18,6 +18,30 @@ pub struct Config {
   pub jwt_secret: String,
   pub data_path: String,
   pub duckling_url: String,
+  #[serde(default)]
+  pub api_keys_path: Option<String>, // json file of static api keys
+  #[serde(default)]
+  pub jwks_path: Option<String>, // json web key set for RS256/ES256 tokens
+  #[serde(default)]
+  pub route_scopes: HashMap<String, String>, // path prefix -> required scope, e.g. "/srl": "srl"
+  #[serde(default)]
+  pub rate_limit_per_minute: Option<u32>, // per subject, or client address without auth
+  #[serde(default)]
+  pub persist_results: bool, // store each text-to-json request and its output in the database
+  #[serde(default = "default_results_admin_scope")]
+  pub results_admin_scope: String, // scope needed to read or re-run other subjects' stored results
+  #[serde(default = "default_srl_url")]
+  pub srl_url: String, // allennlp style srl predict endpoint
+  #[serde(default)]
+  pub srl_cross_check: bool, // use srl to fill in and check the link parser's action roles
+}
+
+fn default_results_admin_scope() -> String {
+  "results:admin".to_string()
+}
+
+fn default_srl_url() -> String {
+  "https://srl.allennlp.sematic.rocks/predict".to_string()
 }
 
 fn init() -> Config {
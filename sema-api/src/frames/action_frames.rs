@@ -0,0 +1,300 @@
+use anyhow::Result;
+use std::fs::File;
+
+use once_cell::sync::OnceCell;
+
+use crate::sema::{
+  action::{Action, ActionFrameMatch, FrameSlotFill},
+  diagnostic::{Diagnostic, DiagnosticKinds, DiagnosticStages},
+  event::EventProperties,
+  sema_sentence::SemaSentence,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SlotTypes {
+  Agent,
+  Entity,
+  Temporal,
+  Location,
+}
+
+impl SlotTypes {
+  fn name(&self) -> &str {
+    match self {
+      SlotTypes::Agent => "agent",
+      SlotTypes::Entity => "entity",
+      SlotTypes::Temporal => "temporal",
+      SlotTypes::Location => "location",
+    }
+  }
+
+  fn plural_name(&self) -> &str {
+    match self {
+      SlotTypes::Agent => "agents",
+      SlotTypes::Entity => "entities",
+      SlotTypes::Temporal => "temporals",
+      SlotTypes::Location => "locations",
+    }
+  }
+}
+
+// A named argument of a frame, e.g. the "buyer" of "buy".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameSlot {
+  pub name: String,
+
+  pub slot_type: SlotTypes,
+
+  // The action properties that fill the slot, e.g. ["theme", "patient"].
+  // Temporal and location slots are also filled from the action's event.
+  #[serde(default)]
+  pub roles: Vec<String>,
+
+  #[serde(default)]
+  pub required: bool,
+}
+
+// A domain action, e.g. "book" or "transfer". Actions are mapped onto a frame by lemma first,
+// then by VerbNet class (subclasses included).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionFrame {
+  pub name: String,
+
+  #[serde(default)]
+  pub lemmas: Vec<String>,
+
+  #[serde(default)]
+  pub verbnet_class: Option<String>,
+
+  pub slots: Vec<FrameSlot>,
+}
+
+impl ActionFrame {
+  pub fn matches_lemma(&self, lemma: &str) -> bool {
+    self
+      .lemmas
+      .iter()
+      .any(|l| l.eq_ignore_ascii_case(lemma))
+  }
+
+  pub fn matches_verbnet_class(&self, verbnet_class: &str) -> bool {
+    match &self.verbnet_class {
+      Some(frame_class) => {
+        verbnet_class == frame_class || verbnet_class.starts_with(&format!("{}-", frame_class))
+      }
+      None => false,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionFrames {
+  pub frames: Vec<ActionFrame>,
+}
+
+impl ActionFrames {
+  pub fn new(data_path: &str) -> Result<Self> {
+    let path = format!("{}/action_frames.ron", data_path);
+    let file = File::open(&path)?;
+    let action_frames: ActionFrames = ron::de::from_reader(file)?;
+
+    Ok(action_frames)
+  }
+
+  pub fn init(data_path: &str) {
+    let action_frames = ActionFrames::new(data_path).expect("Unable to create ActionFrames instance");
+    ACTION_FRAMES
+      .set(action_frames)
+      .expect("Unable to set ACTION_FRAMES");
+  }
+
+  pub fn get_frame(action: &Action) -> Option<&'static ActionFrame> {
+    let action_frames = ACTION_FRAMES
+      .get()
+      .expect("ACTION_FRAMES is not initialized");
+
+    action_frames
+      .frames
+      .iter()
+      .find(|frame| frame.matches_lemma(&action.action_type))
+      .or_else(|| {
+        let verbnet_class = action
+          .verbnet_class
+          .as_deref()?;
+
+        action_frames
+          .frames
+          .iter()
+          .find(|frame| frame.matches_verbnet_class(verbnet_class))
+      })
+  }
+}
+
+pub static ACTION_FRAMES: OnceCell<ActionFrames> = OnceCell::new();
+
+// Maps each action onto its domain frame, and reports required slots that nothing filled
+// and fillers of the wrong type.
+pub fn apply_action_frames(sema_sentence: &SemaSentence) -> Result<SemaSentence> {
+  let mut framed_sentence = sema_sentence.clone();
+  let mut diagnostics = vec![];
+
+  for action in framed_sentence
+    .actions
+    .iter_mut()
+  {
+    let frame = match ActionFrames::get_frame(action) {
+      Some(frame) => frame,
+      None => continue,
+    };
+
+    let mut slots = vec![];
+
+    for slot in frame
+      .slots
+      .iter()
+    {
+      let mut symbols = vec![];
+
+      for s in slot_candidates(sema_sentence, action, slot) {
+        if symbols.contains(&s) {
+          continue;
+        }
+
+        if !is_slot_type(sema_sentence, &s, &slot.slot_type) {
+          diagnostics.push(Diagnostic::new(
+            DiagnosticStages::Frames,
+            DiagnosticKinds::Invalid,
+            format!(
+              "{} can't fill the \"{}\" slot of {} {}, it only takes {}",
+              s,
+              slot.name,
+              frame.name,
+              action.symbol,
+              slot
+                .slot_type
+                .plural_name()
+            ),
+            vec![action.symbol.clone(), s],
+          ));
+          continue;
+        }
+
+        symbols.push(s);
+      }
+
+      if slot.required && symbols.is_empty() {
+        diagnostics.push(Diagnostic::new(
+          DiagnosticStages::Frames,
+          DiagnosticKinds::Missing,
+          format!(
+            "{} {} is missing its required \"{}\" slot ({})",
+            frame.name,
+            action.symbol,
+            slot.name,
+            slot
+              .slot_type
+              .name()
+          ),
+          vec![action.symbol.clone()],
+        ));
+      }
+
+      slots.push(FrameSlotFill {
+        slot: slot.name.clone(),
+        symbols,
+      });
+    }
+
+    action.frame = Some(ActionFrameMatch {
+      name: frame.name.clone(),
+      slots,
+    });
+  }
+
+  framed_sentence
+    .diagnostics
+    .extend(diagnostics);
+
+  Ok(framed_sentence)
+}
+
+fn slot_candidates(sema_sentence: &SemaSentence, action: &Action, slot: &FrameSlot) -> Vec<String> {
+  let mut candidates = vec![];
+
+  // Properties serialize as a single key object, e.g. { "theme": "$4" }.
+  for property in action
+    .properties
+    .iter()
+  {
+    if let Ok(serde_json::Value::Object(map)) = serde_json::to_value(property) {
+      for (role, value) in map {
+        if let Some(s) = value.as_str() {
+          if slot
+            .roles
+            .contains(&role)
+          {
+            candidates.push(s.to_string());
+          }
+        }
+      }
+    }
+  }
+
+  if matches!(slot.slot_type, SlotTypes::Temporal | SlotTypes::Location) {
+    for event in sema_sentence
+      .events
+      .iter()
+      .filter(|event| {
+        event
+          .properties
+          .iter()
+          .any(|p| matches!(p, EventProperties::Action { action: a } if *a == action.symbol))
+      })
+    {
+      for p in event
+        .properties
+        .iter()
+      {
+        match (p, &slot.slot_type) {
+          (EventProperties::Occurance { occurs: s }, SlotTypes::Temporal)
+          | (EventProperties::Duration { duration: s }, SlotTypes::Temporal)
+          | (EventProperties::Recurrence { recurs: s }, SlotTypes::Temporal)
+          | (EventProperties::Location { location: s }, SlotTypes::Location) => {
+            candidates.push(s.clone())
+          }
+          _ => (),
+        }
+      }
+    }
+  }
+
+  candidates
+}
+
+fn is_slot_type(sema_sentence: &SemaSentence, s: &str, slot_type: &SlotTypes) -> bool {
+  let is_agent = sema_sentence
+    .agents
+    .iter()
+    .any(|a| a.get_symbol() == s);
+  let is_entity = sema_sentence
+    .entities
+    .iter()
+    .any(|e| e.symbol == s);
+
+  match slot_type {
+    SlotTypes::Agent => is_agent,
+    SlotTypes::Entity => is_entity,
+    SlotTypes::Temporal => sema_sentence
+      .temporal
+      .iter()
+      .any(|t| t.get_symbol() == s),
+    // Places are often plain entities, e.g. "to the office".
+    SlotTypes::Location => {
+      is_entity
+        || sema_sentence
+          .locations
+          .iter()
+          .any(|l| l.get_symbol() == s)
+    }
+  }
+}
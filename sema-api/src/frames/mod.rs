@@ -0,0 +1,7 @@
+pub mod action_frames;
+
+use self::action_frames::ActionFrames;
+
+pub fn init_frames_cells(data_path: &str) {
+  ActionFrames::init(data_path);
+}
@@ -3,15 +3,21 @@ extern crate serde_derive;
 extern crate derive_more;
 extern crate serde_json;
 
+pub mod adjectives;
+pub mod built_info;
 pub mod config;
+pub mod frames;
 pub mod nlp;
 pub mod parse;
 pub mod process_sentences;
+pub mod propbank;
 pub mod routes;
+pub mod schema;
 pub mod sema;
 pub mod sentence;
 pub mod services;
 pub mod state;
 pub mod wordnet;
+pub mod verbnet;
 pub mod verify;
 pub mod middleware;
\ No newline at end of file
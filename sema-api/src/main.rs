@@ -3,34 +3,45 @@ extern crate serde_derive;
 extern crate derive_more;
 extern crate serde_json;
 
+pub mod adjectives;
+pub mod built_info;
 pub mod config;
+pub mod frames;
 pub mod middleware;
 pub mod nlp;
 pub mod parse;
 pub mod process_sentences;
+pub mod propbank;
 pub mod routes;
+pub mod schema;
 pub mod sema;
 pub mod sentence;
 pub mod services;
 pub mod state;
+pub mod verbnet;
 pub mod verify;
 pub mod wordnet;
 
 use futures::lock::Mutex;
 use sema_api::config::{server_config, Config};
-// use sqlx::postgres::PgPoolOptions;
-use std::sync::Arc;
-// use std::{sync::Arc, time::Duration};
+use sqlx::postgres::PgPoolOptions;
+use std::{sync::Arc, time::Duration};
 
 use actix_web::{web, App, HttpServer};
 use actix_cors::Cors;
 use link_parser_rust_bindings::{LinkParser, LinkParserOptions};
 
-use routes::{health, srl, text_to_json};
-// use state::State;
+use routes::results::{get_result, list_results, rerun_result};
+use routes::{health, json_schema, openapi_spec, srl, text_to_json};
+use state::State;
 
 use crate::middleware::auth::validator;
+use crate::adjectives::init_adjectives_cells;
+use crate::frames::init_frames_cells;
+use crate::middleware::init_auth_cells;
 use crate::nlp::init_nlp_cells;
+use crate::propbank::init_propbank_cells;
+use crate::verbnet::init_verbnet_cells;
 use crate::wordnet::init_wordnet_cells;
 use actix_web_httpauth::middleware::HttpAuthentication;
 use jsonwebtoken::DecodingKey;
@@ -67,17 +78,27 @@ async fn main() -> std::io::Result<()> {
 
   init_nlp_cells(&config.data_path);
   init_wordnet_cells(&config.data_path);
+  init_verbnet_cells(&config.data_path);
+  init_propbank_cells(&config.data_path);
+  init_frames_cells(&config.data_path);
+  init_adjectives_cells(&config.data_path);
+  init_auth_cells();
 
   dbg!(&config);
 
-  // let pool = PgPoolOptions::new()
-  //   .max_connections(config.database_connection_pool_size)
-  //   .connect_timeout(Duration::new(config.database_connection_timeout_sec, 0))
-  //   .connect(&config.database_url)
-  //   .await
-  //   .expect("Error connecting to database");
-
-  // let state = State::new(pool.clone());
+  // The database is only needed for storing parse results, so only connect when that's turned on.
+  let state = if config.persist_results {
+    let pool = PgPoolOptions::new()
+      .max_connections(config.database_connection_pool_size)
+      .connect_timeout(Duration::new(config.database_connection_timeout_sec, 0))
+      .connect(&config.database_url)
+      .await
+      .expect("Error connecting to database");
+
+    Some(State::new(pool))
+  } else {
+    None
+  };
 
   HttpServer::new(move || {
     let decoding_key = DecodingKey::from_secret(
@@ -86,16 +107,26 @@ async fn main() -> std::io::Result<()> {
         .as_bytes(),
     );
 
-    App::new()
+    let mut app = App::new()
       .wrap(actix_web::middleware::Logger::new("%s for %U %a in %Ts"))
       .wrap(HttpAuthentication::bearer(validator))
       .app_data(web::Data::new(link_parser.clone()))
-      // .app_data(web::Data::new(state.clone()))
       .wrap(get_cors(config))
-      .app_data(decoding_key)
+      .app_data(decoding_key);
+
+    if let Some(state) = &state {
+      app = app.app_data(web::Data::new(state.clone()));
+    }
+
+    app
       .service(health)
+      .service(json_schema)
+      .service(openapi_spec)
       .service(srl)
       .service(text_to_json)
+      .service(get_result)
+      .service(list_results)
+      .service(rerun_result)
   })
   .bind(format!("0.0.0.0:{}", config.tcp_port))?
   .run()
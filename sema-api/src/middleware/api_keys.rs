@@ -0,0 +1,58 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+
+use once_cell::sync::OnceCell;
+
+// A single static API key, as stored in the api keys file.
+// e.g. { "key": "sk_123", "subject": "reporting-job", "scopes": ["srl"], "rate_limit_per_minute": 60 }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+  pub key: String,
+
+  pub subject: String,
+
+  #[serde(default)]
+  pub scopes: Vec<String>,
+
+  // overrides the server wide `rate_limit_per_minute` for this key's subject.
+  pub rate_limit_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeys {
+  // key: the api key itself
+  keys: HashMap<String, ApiKey>,
+}
+
+impl ApiKeys {
+  pub fn new(path: &str) -> Result<Self> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let api_keys: Vec<ApiKey> = serde_json::from_reader(reader)?;
+
+    let keys = api_keys
+      .into_iter()
+      .map(|api_key| (api_key.key.clone(), api_key))
+      .collect::<HashMap<String, ApiKey>>();
+
+    Ok(ApiKeys { keys })
+  }
+
+  pub fn init(path: &str) {
+    let api_keys = ApiKeys::new(path).expect("Unable to create ApiKeys instance");
+    API_KEYS
+      .set(api_keys)
+      .expect("Unable to set API_KEYS");
+  }
+
+  // Returns None when no api keys file was configured.
+  pub fn get(key: &str) -> Option<&'static ApiKey> {
+    API_KEYS
+      .get()
+      .and_then(|api_keys| api_keys.keys.get(key))
+  }
+}
+
+pub static API_KEYS: OnceCell<ApiKeys> = OnceCell::new();
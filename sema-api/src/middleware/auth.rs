@@ -1,15 +1,64 @@
 use actix_web::dev::ServiceRequest;
-use actix_web::Error;
+use actix_web::error::{ErrorForbidden, ErrorTooManyRequests};
+use actix_web::{Error, HttpMessage};
 use actix_web_httpauth::extractors::bearer::{BearerAuth, Config};
 use actix_web_httpauth::extractors::AuthenticationError;
-use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
 
+use super::api_keys::ApiKeys;
+use super::jwks::Jwks;
+use super::rate_limit::check_rate_limit;
 use crate::config::server_config;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
-  sub: String,
-  exp: usize,
+  pub sub: String,
+  pub exp: usize,
+  // space separated, e.g. "srl admin"
+  pub scope: Option<String>,
+}
+
+// The authenticated caller of a request.
+// Handlers can get at it with `web::ReqData<AuthSubject>`, e.g. for auditing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSubject {
+  pub subject: String,
+  pub scopes: Vec<String>,
+  pub rate_limit_per_minute: Option<u32>,
+}
+
+impl AuthSubject {
+  pub fn anonymous() -> Self {
+    AuthSubject {
+      subject: "anonymous".to_string(),
+      scopes: vec![],
+      rate_limit_per_minute: None,
+    }
+  }
+
+  pub fn from_claims(claims: Claims) -> Self {
+    let scopes = claims
+      .scope
+      .map(|s| {
+        s.split_whitespace()
+          .map(|scope| scope.to_string())
+          .collect::<Vec<String>>()
+      })
+      .unwrap_or_default();
+
+    AuthSubject {
+      subject: claims.sub,
+      scopes,
+      rate_limit_per_minute: None,
+    }
+  }
+
+  pub fn has_scope(&self, scope: &str) -> bool {
+    self
+      .scopes
+      .iter()
+      .any(|s| s == scope)
+  }
 }
 
 pub async fn validator(
@@ -19,26 +68,127 @@ pub async fn validator(
   let app_config = server_config();
 
   if !app_config.use_jwt_auth {
-    return Ok(req);
-  }
+    // Anonymous callers share a subject, so they're limited by their address instead. Behind a
+    // proxy that's the proxy's address.
+    if let Some(limit) = app_config.rate_limit_per_minute {
+      let client = req
+        .peer_addr()
+        .map(|addr| {
+          addr
+            .ip()
+            .to_string()
+        })
+        .unwrap_or_default();
 
-  let key = req
-    .app_data::<DecodingKey>()
-    .unwrap();
+      if !check_rate_limit(&format!("anonymous:{}", client), limit) {
+        return Err((ErrorTooManyRequests("rate limit exceeded"), req));
+      }
+    }
 
-  let mut validation = Validation::new(Algorithm::HS256);
-  validation.leeway = 120;
+    req
+      .extensions_mut()
+      .insert(AuthSubject::anonymous());
+    return Ok(req);
+  }
 
-  match decode::<Claims>(credentials.token(), &key, &validation) {
-    Ok(_) => Ok(req),
-    Err(err) => {
-      println!("valdation err: {:?}", err);
+  let auth_subject = match authenticate(&req, credentials.token()) {
+    Some(auth_subject) => auth_subject,
+    None => {
       // TODO: figure out how to better handle this error;
       let config = req
         .app_data::<Config>()
         .map(|data| data.clone())
         .unwrap_or_else(Default::default);
-      Err((AuthenticationError::from(config).into(), req))
+      return Err((AuthenticationError::from(config).into(), req));
+    }
+  };
+
+  // Per-route scopes. Keys are path prefixes, e.g. "/srl" or "/admin".
+  for (route_prefix, scope) in app_config
+    .route_scopes
+    .iter()
+  {
+    if route_matches(req.path(), route_prefix) && !auth_subject.has_scope(scope) {
+      return Err((
+        ErrorForbidden(format!("missing required scope: {}", scope)),
+        req,
+      ));
+    }
+  }
+
+  let rate_limit = auth_subject
+    .rate_limit_per_minute
+    .or(app_config.rate_limit_per_minute);
+
+  if let Some(limit) = rate_limit {
+    if !check_rate_limit(&auth_subject.subject, limit) {
+      return Err((ErrorTooManyRequests("rate limit exceeded"), req));
+    }
+  }
+
+  req
+    .extensions_mut()
+    .insert(auth_subject);
+
+  Ok(req)
+}
+
+// Whether the path is the prefix or under it, by whole segments, so "/srl" covers "/srl" and
+// "/srl/batch" but not "/srlx".
+fn route_matches(path: &str, route_prefix: &str) -> bool {
+  let prefix = route_prefix.trim_end_matches('/');
+
+  path == prefix
+    || path
+      .strip_prefix(prefix)
+      .map(|rest| rest.starts_with('/'))
+      .unwrap_or(false)
+}
+
+// Static API keys are checked first, then JWTs.
+fn authenticate(req: &ServiceRequest, token: &str) -> Option<AuthSubject> {
+  if let Some(api_key) = ApiKeys::get(token) {
+    return Some(AuthSubject {
+      subject: api_key
+        .subject
+        .clone(),
+      scopes: api_key
+        .scopes
+        .clone(),
+      rate_limit_per_minute: api_key.rate_limit_per_minute,
+    });
+  }
+
+  let header = match decode_header(token) {
+    Ok(header) => header,
+    Err(err) => {
+      log::warn!("token validation error: {:?}", err);
+      return None;
+    }
+  };
+
+  let key = match header.alg {
+    Algorithm::HS256 => req
+      .app_data::<DecodingKey>()
+      .cloned(),
+    Algorithm::RS256 | Algorithm::ES256 => match Jwks::decoding_key(header.kid.as_deref()) {
+      Ok(key) => Some(key),
+      Err(err) => {
+        log::warn!("token validation error: {:?}", err);
+        None
+      }
+    },
+    _ => None,
+  }?;
+
+  let mut validation = Validation::new(header.alg);
+  validation.leeway = 120;
+
+  match decode::<Claims>(token, &key, &validation) {
+    Ok(token_data) => Some(AuthSubject::from_claims(token_data.claims)),
+    Err(err) => {
+      log::warn!("token validation error: {:?}", err);
+      None
     }
   }
 }
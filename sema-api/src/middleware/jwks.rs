@@ -0,0 +1,61 @@
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::BufReader;
+
+use jsonwebtoken::{jwk::JwkSet, DecodingKey};
+use once_cell::sync::OnceCell;
+
+// Public keys used to verify asymmetric (RS256/ES256) tokens.
+// Loaded from a local JWKS file, so no network calls are made during auth.
+#[derive(Debug, Clone)]
+pub struct Jwks {
+  key_set: JwkSet,
+}
+
+impl Jwks {
+  pub fn new(path: &str) -> Result<Self> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let key_set: JwkSet = serde_json::from_reader(reader)?;
+
+    Ok(Jwks { key_set })
+  }
+
+  pub fn init(path: &str) {
+    let jwks = Jwks::new(path).expect("Unable to create Jwks instance");
+    JWKS
+      .set(jwks)
+      .expect("Unable to set JWKS");
+  }
+
+  // Finds the decoding key for a token's "kid" header.
+  // If the token has no "kid", and the set only has one key, that key is used.
+  pub fn decoding_key(kid: Option<&str>) -> Result<DecodingKey> {
+    let jwks = JWKS
+      .get()
+      .ok_or_else(|| anyhow!("no jwks_path configured for asymmetric tokens"))?;
+
+    let jwk = match kid {
+      Some(kid) => jwks
+        .key_set
+        .find(kid),
+      None if jwks
+        .key_set
+        .keys
+        .len()
+        == 1 =>
+      {
+        jwks
+          .key_set
+          .keys
+          .first()
+      }
+      None => None,
+    }
+    .ok_or_else(|| anyhow!("no matching key found in jwks for kid: {:?}", kid))?;
+
+    Ok(DecodingKey::from_jwk(jwk)?)
+  }
+}
+
+pub static JWKS: OnceCell<Jwks> = OnceCell::new();
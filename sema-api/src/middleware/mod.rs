@@ -1 +1,20 @@
-pub mod auth;
\ No newline at end of file
+pub mod api_keys;
+pub mod auth;
+pub mod jwks;
+pub mod rate_limit;
+
+use self::api_keys::ApiKeys;
+use self::jwks::Jwks;
+use crate::config::server_config;
+
+pub fn init_auth_cells() {
+  let config = server_config();
+
+  if let Some(api_keys_path) = &config.api_keys_path {
+    ApiKeys::init(api_keys_path);
+  }
+
+  if let Some(jwks_path) = &config.jwks_path {
+    Jwks::init(jwks_path);
+  }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+struct Window {
+  started: Instant,
+  count: u32,
+}
+
+#[derive(Debug)]
+struct Windows {
+  by_key: HashMap<String, Window>,
+  // when expired windows were last removed
+  swept: Instant,
+}
+
+// Fixed window request counter, keyed by the authenticated subject, or the client address for
+// anonymous callers. Kept in memory, so limits are per server instance.
+static WINDOWS: Lazy<Mutex<Windows>> = Lazy::new(|| {
+  Mutex::new(Windows {
+    by_key: HashMap::new(),
+    swept: Instant::now(),
+  })
+});
+
+// Records a request for the key, and returns false if the key is over its limit.
+pub fn check_rate_limit(key: &str, limit_per_minute: u32) -> bool {
+  let mut windows = WINDOWS
+    .lock()
+    .expect("rate limit windows lock poisoned");
+
+  let now = Instant::now();
+
+  // Callers that stopped sending requests would otherwise keep their windows forever.
+  if now.duration_since(windows.swept) >= WINDOW {
+    windows
+      .by_key
+      .retain(|_, window| now.duration_since(window.started) < WINDOW);
+    windows.swept = now;
+  }
+
+  let window = windows
+    .by_key
+    .entry(key.to_string())
+    .or_insert(Window {
+      started: now,
+      count: 0,
+    });
+
+  if now.duration_since(window.started) >= WINDOW {
+    window.started = now;
+    window.count = 0;
+  }
+
+  if window.count >= limit_per_minute {
+    return false;
+  }
+
+  window.count += 1;
+
+  true
+}
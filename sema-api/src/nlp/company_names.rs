@@ -0,0 +1,74 @@
+use anyhow::Result;
+use std::fs::File;
+
+use once_cell::sync::OnceCell;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanyName {
+  pub name: String,
+
+  #[serde(default)]
+  pub aliases: Vec<String>,
+
+  #[serde(default)]
+  pub industry: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompanyNames {
+  pub companies: Vec<CompanyName>,
+}
+
+impl CompanyNames {
+  pub fn new(data_path: &str) -> Result<Self> {
+    let path = format!("{}/company_names.ron", data_path);
+    let file = File::open(&path)?;
+    let company_names: CompanyNames = ron::de::from_reader(file)?;
+
+    Ok(company_names)
+  }
+
+  pub fn init(data_path: &str) {
+    let company_names = CompanyNames::new(data_path).expect("Unable to create CompanyNames instance");
+    COMPANY_NAMES
+      .set(company_names)
+      .expect("Unable to set COMPANY_NAMES");
+  }
+
+  // The company with the longest name (or alias) that the words start with, and how many words
+  // it takes up, e.g. (2, Goldman Sachs) for ["goldman", "sachs", "hired", "jane"].
+  pub fn longest_match(words: &[String]) -> Option<(usize, &'static CompanyName)> {
+    let company_names = COMPANY_NAMES
+      .get()
+      .expect("COMPANY_NAMES is not initialized");
+
+    company_names
+      .companies
+      .iter()
+      .flat_map(|company| {
+        std::iter::once(&company.name)
+          .chain(&company.aliases)
+          .map(move |name| (name, company))
+      })
+      .filter_map(|(name, company)| {
+        let name_words = name
+          .split_whitespace()
+          .collect::<Vec<&str>>();
+
+        let matches = name_words.len() <= words.len()
+          && name_words
+            .iter()
+            .zip(words)
+            .all(|(n, w)| n.eq_ignore_ascii_case(w));
+
+        if matches {
+          Some((name_words.len(), company))
+        } else {
+          None
+        }
+      })
+      .max_by_key(|(len, _)| *len)
+  }
+}
+
+pub static COMPANY_NAMES: OnceCell<CompanyNames> = OnceCell::new();
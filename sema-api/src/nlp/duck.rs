@@ -29,6 +29,8 @@ impl From<DucklingParseResponse> for DuckPart {
       DucklingValueOption::Value {
         grain,
         value,
+        unit,
+        product,
         values,
       } => {
         if let Some(v) = values {
@@ -36,7 +38,7 @@ impl From<DucklingParseResponse> for DuckPart {
             .for_each(|v| part_values.push(v.into()));
         };
 
-        DuckValues::Value { grain, value }
+        DuckValues::new(grain, value, unit, product)
       }
       DucklingValueOption::Interval { to, from, values } => {
         if let Some(v) = values {
@@ -65,13 +67,15 @@ impl From<DucklingParseResponse> for DuckPart {
 pub struct DuckValue {
   pub value: String,
   pub grain: String,
+  pub unit: Option<String>,
 }
 
 impl From<NoTypeValue> for DuckValue {
   fn from(no_type_value: NoTypeValue) -> Self {
     DuckValue {
-      value: no_type_value.value,
+      value: value_string(&no_type_value.value),
       grain: no_type_value.grain,
+      unit: no_type_value.unit,
     }
   }
 }
@@ -79,17 +83,49 @@ impl From<NoTypeValue> for DuckValue {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DuckValues {
   Value { grain: String, value: String },
+  // numbers, quantities and money, e.g. 2 "cup" of "flour", or 12.5 "$"
+  Measure { value: f64, unit: Option<String>, product: Option<String> },
   Interval { to: DuckValue, from: DuckValue },
 }
 
+impl DuckValues {
+  pub fn new(
+    grain: String,
+    value: serde_json::Value,
+    unit: Option<String>,
+    product: Option<String>,
+  ) -> Self {
+    match value.as_f64() {
+      Some(value) => DuckValues::Measure {
+        value,
+        unit,
+        product,
+      },
+      None => DuckValues::Value {
+        grain,
+        value: value_string(&value),
+      },
+    }
+  }
+}
+
+fn value_string(value: &serde_json::Value) -> String {
+  match value.as_str() {
+    Some(s) => s.to_string(),
+    None => value.to_string(),
+  }
+}
+
 impl From<DucklingValueOption> for DuckValues {
   fn from(duckling_val_opt: DucklingValueOption) -> Self {
     match duckling_val_opt {
       DucklingValueOption::Value {
         grain,
         value,
+        unit,
+        product,
         values: _,
-      } => DuckValues::Value { grain, value },
+      } => DuckValues::new(grain, value, unit, product),
       DucklingValueOption::Interval {
         to,
         from,
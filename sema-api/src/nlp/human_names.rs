@@ -5,9 +5,33 @@ use std::io::BufReader;
 
 use once_cell::sync::OnceCell;
 
+use crate::sema::agents::Genders;
+
+// A name's entry: the gender it's given to, e.g. {"gender": "female"}, or just a count for names
+// with no gender, like surnames.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum NameEntry {
+  Gender { gender: NameGender },
+  Count(usize),
+}
+
+// Which given name list a name is on. Unisex names are given to both, so they don't tell a
+// person's gender.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum NameGender {
+  Male,
+  Female,
+  Unisex,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HumanNames {
   names: HashSet<String>,
+  // Names with a gender entry, lowercased, e.g. "emma"
+  given_names: HashSet<String>,
+  genders: HashMap<String, Genders>,
 }
 
 impl HumanNames {
@@ -16,11 +40,40 @@ impl HumanNames {
     println!("path: {}", path);
     let names = File::open(&path)?;
     let reader = BufReader::new(names);
-    let json: HashMap<String, usize> = serde_json::from_reader(reader)?;
+    let json: HashMap<String, NameEntry> = serde_json::from_reader(reader)?;
+
+    let mut names = HashSet::new();
+    let mut given_names = HashSet::new();
+    let mut genders = HashMap::new();
+
+    for (name, entry) in json {
+      if let NameEntry::Gender { .. } = entry {
+        given_names.insert(name.to_lowercase());
+      }
 
-    let names = json.into_keys().collect::<HashSet<String>>();
+      let gender = match entry {
+        NameEntry::Gender {
+          gender: NameGender::Male,
+        } => Some(Genders::Male),
+        NameEntry::Gender {
+          gender: NameGender::Female,
+        } => Some(Genders::Female),
+        _ => None,
+      };
 
-    Ok(HumanNames { names })
+      // Genders are looked up whatever the case, e.g. "JOHN" or "john".
+      if let Some(gender) = gender {
+        genders.insert(name.to_lowercase(), gender);
+      }
+
+      names.insert(name);
+    }
+
+    Ok(HumanNames {
+      names,
+      given_names,
+      genders,
+    })
   }
 
   pub fn init(data_path: &str) {
@@ -28,13 +81,33 @@ impl HumanNames {
     HUMAN_NAMES.set(human_names).expect("Unable to set HUMAN_NAMES");
   }
 
+  // Names are capitalized, but a shouted given name is a name too, e.g. "EMMA". Other names in
+  // capitals are more likely acronyms, e.g. "NASA".
   pub fn contains(possible_name: &str) -> bool {
     let human_names = HUMAN_NAMES
       .get()
       .expect("HUMAN_NAMES is not initialized");
-      human_names
+
+    human_names
       .names
       .contains(possible_name)
+      || (possible_name.chars().count() > 1
+        && possible_name == possible_name.to_uppercase()
+        && human_names
+          .given_names
+          .contains(&possible_name.to_lowercase()))
+  }
+
+  // The gender a given name is given to. Unisex names aren't given one.
+  pub fn gender(name: &str) -> Option<Genders> {
+    let human_names = HUMAN_NAMES
+      .get()
+      .expect("HUMAN_NAMES is not initialized");
+
+    human_names
+      .genders
+      .get(&name.to_lowercase())
+      .cloned()
   }
 }
 
@@ -3,12 +3,19 @@ pub mod treebank_pos;
 pub mod sentence_parts;
 pub mod chunk;
 pub mod human_names;
+pub mod company_names;
+pub mod person_names;
+pub mod person_attributes;
 pub mod duck;
 
 use self::nlp_rule::NLPRule;
 use self::human_names::HumanNames;
+use self::company_names::CompanyNames;
+use self::person_attributes::PersonAttributes;
 
 pub fn init_nlp_cells(data_path: &str) {
   NLPRule::init();
   HumanNames::init(data_path);
+  CompanyNames::init(data_path);
+  PersonAttributes::init(data_path);
 }
\ No newline at end of file
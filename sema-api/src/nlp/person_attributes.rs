@@ -0,0 +1,52 @@
+use anyhow::Result;
+use std::fs::File;
+
+use once_cell::sync::OnceCell;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonAttributes {
+  pub occupations: Vec<String>,
+  pub nationalities: Vec<String>,
+}
+
+impl PersonAttributes {
+  pub fn new(data_path: &str) -> Result<Self> {
+    let path = format!("{}/person_attributes.ron", data_path);
+    let file = File::open(&path)?;
+    let person_attributes: PersonAttributes = ron::de::from_reader(file)?;
+
+    Ok(person_attributes)
+  }
+
+  pub fn init(data_path: &str) {
+    let person_attributes =
+      PersonAttributes::new(data_path).expect("Unable to create PersonAttributes instance");
+    PERSON_ATTRIBUTES
+      .set(person_attributes)
+      .expect("Unable to set PERSON_ATTRIBUTES");
+  }
+
+  pub fn is_occupation(word: &str) -> bool {
+    let person_attributes = PERSON_ATTRIBUTES
+      .get()
+      .expect("PERSON_ATTRIBUTES is not initialized");
+
+    person_attributes
+      .occupations
+      .iter()
+      .any(|o| o == word)
+  }
+
+  pub fn is_nationality(word: &str) -> bool {
+    let person_attributes = PERSON_ATTRIBUTES
+      .get()
+      .expect("PERSON_ATTRIBUTES is not initialized");
+
+    person_attributes
+      .nationalities
+      .iter()
+      .any(|n| n == word)
+  }
+}
+
+pub static PERSON_ATTRIBUTES: OnceCell<PersonAttributes> = OnceCell::new();
@@ -0,0 +1,187 @@
+use crate::sema::agents::PersonProperties;
+
+// Honorifics in front of a name, e.g. "Dr" in "Dr. Martin Luther King Jr.".
+pub static TITLES: [&str; 24] = [
+  "mr",
+  "mrs",
+  "ms",
+  "miss",
+  "mister",
+  "madam",
+  "mme",
+  "mlle",
+  "sir",
+  "dame",
+  "dr",
+  "prof",
+  "professor",
+  "rev",
+  "fr",
+  "capt",
+  "col",
+  "gen",
+  "lt",
+  "sgt",
+  "sen",
+  "rep",
+  "gov",
+  "hon",
+];
+
+// Lowercase words that start a surname, e.g. "van" in "Ludwig van Beethoven". They're also
+// hyphenated onto it, e.g. "al-" in "Bashar al-Assad".
+pub static SURNAME_PARTICLES: [&str; 21] = [
+  "van", "von", "der", "den", "ter", "ten", "de", "del", "della", "di", "da", "du", "des", "la",
+  "le", "dos", "das", "bin", "ibn", "al", "el",
+];
+
+// Generational and professional suffixes after a name, e.g. "Jr" or "III".
+pub static NAME_SUFFIXES: [&str; 9] = ["jr", "sr", "ii", "iii", "iv", "esq", "phd", "md", "dds"];
+
+// A person's name, split into its parts.
+#[derive(Debug, Clone, Default)]
+pub struct PersonName {
+  pub titles: Vec<String>,
+  pub given_names: Vec<String>,
+  pub surname: Vec<String>,
+  pub suffixes: Vec<String>,
+}
+
+impl PersonName {
+  // The words of a name, as they were written:
+  // - titles come first, and suffixes last,
+  // - the surname starts at the first particle, or is the last word,
+  // - the given names before it are a first name and middle names (or initials),
+  // - a lone name is a last name after a title ("Mr. Smith"), and a name otherwise ("John").
+  pub fn parse(words: &[String]) -> Self {
+    let mut words = words
+      .iter()
+      .map(|w| normalize_name_part(w))
+      .filter(|w| !w.is_empty())
+      .collect::<Vec<String>>();
+
+    let mut name = Self::default();
+
+    while words.len() > 1 && is_title(&words[0]) {
+      name
+        .titles
+        .push(words.remove(0));
+    }
+
+    while words.len() > 1 && is_name_suffix(&words[words.len() - 1]) {
+      let suffix = words
+        .pop()
+        .unwrap();
+
+      name
+        .suffixes
+        .insert(0, suffix);
+    }
+
+    let surname_start = match words
+      .iter()
+      .position(|w| is_surname_particle(w))
+    {
+      Some(p) => p,
+      None if words.len() > 1 => words.len() - 1,
+      None if !name.titles.is_empty() => 0,
+      None => words.len(),
+    };
+
+    name.surname = words.split_off(surname_start);
+    name.given_names = words;
+
+    name
+  }
+
+  pub fn properties(&self) -> Vec<PersonProperties> {
+    let mut properties = self
+      .titles
+      .iter()
+      .map(|title| PersonProperties::Title {
+        title: title.clone(),
+      })
+      .collect::<Vec<PersonProperties>>();
+
+    if self
+      .surname
+      .is_empty()
+    {
+      properties.push(PersonProperties::Name {
+        name: self
+          .given_names
+          .join("_"),
+      });
+    } else {
+      if let Some((first_name, middle_names)) = self
+        .given_names
+        .split_first()
+      {
+        properties.push(PersonProperties::FirstName {
+          first_name: first_name.clone(),
+        });
+
+        properties.extend(
+          middle_names
+            .iter()
+            .map(|middle_name| PersonProperties::MiddleName {
+              middle_name: middle_name.clone(),
+            }),
+        );
+      }
+
+      properties.push(PersonProperties::LastName {
+        last_name: self
+          .surname
+          .join("_"),
+      });
+    }
+
+    properties.extend(
+      self
+        .suffixes
+        .iter()
+        .map(|suffix| PersonProperties::Suffix {
+          suffix: suffix.clone(),
+        }),
+    );
+
+    properties
+  }
+}
+
+// "Dr." -> "dr", "F." -> "f"
+fn normalize_name_part(word: &str) -> String {
+  word
+    .trim_end_matches(|c| c == '.' || c == ',')
+    .to_lowercase()
+}
+
+pub fn is_title(word: &str) -> bool {
+  TITLES.contains(&normalize_name_part(word).as_str())
+}
+
+pub fn is_name_suffix(word: &str) -> bool {
+  NAME_SUFFIXES.contains(&normalize_name_part(word).as_str())
+}
+
+pub fn is_surname_particle(word: &str) -> bool {
+  let word = normalize_name_part(word);
+
+  match word.split_once('-') {
+    Some((particle, rest)) => SURNAME_PARTICLES.contains(&particle) && !rest.is_empty(),
+    None => SURNAME_PARTICLES.contains(&word.as_str()),
+  }
+}
+
+// "F." in "John F. Kennedy"
+pub fn is_initial(word: &str) -> bool {
+  let mut chars = word
+    .trim_end_matches('.')
+    .chars();
+
+  matches!(
+    (chars.next(), chars.next()),
+    (Some(c), None) if c.is_ascii_uppercase()
+  )
+}
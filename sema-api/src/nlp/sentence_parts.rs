@@ -1,12 +1,14 @@
 use super::duck::DuckPart;
 use super::nlp_rule::NLPRule;
 use super::{chunk::Chunk, duck::Duck};
+use crate::services::allennlp_service::SRLResponse;
 use anyhow::Result;
 use link_parser_rust_bindings::lp::{sentence::Sentence as LPSentence, word::Word as LPWord};
 use nlprule::types::owned::Token;
+use schemars::JsonSchema;
 use urlencoding::decode;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum SentenceEncodings {
   #[serde(rename = "none")]
   None,
@@ -52,6 +54,22 @@ impl SentenceText {
   }
 }
 
+// A compact view of SentenceParts, small enough to store alongside each parse result.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SentencePartsSummary {
+  pub original_sentence: String,
+
+  pub corrected_sentence: String,
+
+  pub lemmatized_sentence: String,
+
+  // e.g. "bake.v Ss- Os+", empty if the link parser found no linkage
+  pub linkage: Vec<String>,
+
+  // e.g. "time: on January 21 , 1990"
+  pub ducklings: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SentenceParts {
   pub original_sentence: String,
@@ -67,6 +85,10 @@ pub struct SentenceParts {
   pub chunks: Vec<Chunk>,
 
   pub duck: Duck,
+
+  // Only filled in when srl_cross_check is turned on.
+  #[serde(default)]
+  pub srl: Option<SRLResponse>,
 }
 
 impl SentenceParts {
@@ -205,9 +227,50 @@ impl SentenceParts {
       },
       chunks,
       duck: Duck::default(),
+      srl: None,
     })
   }
 
+  pub fn summary(&self) -> SentencePartsSummary {
+    let linkage = self
+      .links
+      .words
+      .iter()
+      .map(|word| {
+        let disjuncts = word
+          .disjuncts
+          .iter()
+          .map(|disjunct| disjunct.raw.as_str())
+          .collect::<Vec<&str>>();
+
+        format!("{} {}", word.word, disjuncts.join(" "))
+          .trim()
+          .to_string()
+      })
+      .collect::<Vec<String>>();
+
+    let ducklings = self
+      .duck
+      .parts
+      .iter()
+      .map(|duck| format!("{}: {}", duck.dim, duck.content))
+      .collect::<Vec<String>>();
+
+    SentencePartsSummary {
+      original_sentence: self
+        .original_sentence
+        .clone(),
+      corrected_sentence: self
+        .corrected_sentence
+        .clone(),
+      lemmatized_sentence: self
+        .lemmatized_sentence
+        .clone(),
+      linkage,
+      ducklings,
+    }
+  }
+
   pub fn get_chunk_tokens(&self, chunk_index: usize) -> Vec<Token> {
     let chunk = &self.chunks[chunk_index];
     let start = chunk.start;
@@ -7,14 +7,24 @@ use link_parser_rust_bindings::lp::{
 use std::collections::HashMap;
 
 use super::{
-  parse_actions::parse_actions, parse_agents::parse_agents, parse_entities::parse_entities,
-  parse_events::parse_events, parse_queries::parse_queries, parse_temporal::parse_temporal,
+  parse_actions::parse_actions, parse_agents::parse_agents, parse_args::assign_propbank_args,
+  parse_clauses::parse_clauses,
+  parse_contacts::parse_contacts, parse_coordination::{
+    coordinated_symbols, coordination_cluster, is_conjunction, parse_coordination,
+    verb_conjunctions,
+  },
+  parse_entities::parse_entities, parse_events::parse_events,
+  parse_measurements::parse_measurements, parse_queries::parse_queries,
+  parse_recurrence::parse_recurrence, parse_srl::cross_check_srl,
+  parse_temporal::parse_temporal,
+  parse_tense::{auxiliary_chain, is_infinitive_auxiliary, passive_auxiliary},
 };
 
 use crate::{
   nlp::sentence_parts::SentenceParts,
   // parse,
   sema::{action::ActionProperties, sema_sentence::SemaSentence, symbol::Symbol},
+  verbnet::verbnet_classes::{ArgumentSlots, VerbNetClass, VerbNetClasses},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +47,12 @@ impl ParseState {
       .insert(symbol.to_string(), positions);
   }
 
+  pub fn remove_symbol(&mut self, symbol: &str) -> Option<Vec<usize>> {
+    self
+      .symbol_positions
+      .remove(symbol)
+  }
+
   pub fn get_symbol_positions(&self, symbol: &str) -> Option<&Vec<usize>> {
     self
       .symbol_positions
@@ -67,15 +83,29 @@ pub fn parse_with_links(part: SentenceParts) -> Result<Option<SemaSentence>> {
 
   let sema_sentence = parse_entities(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
 
+  let sema_sentence = parse_measurements(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
+
+  let sema_sentence = parse_contacts(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
+
+  let sema_sentence = parse_coordination(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
+
   let sema_sentence = parse_temporal(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
 
   let sema_sentence = parse_events(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
 
+  let sema_sentence = parse_recurrence(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
+
   let sema_sentence = parse_queries(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
 
   // // Connect up all the objects created earlier
   let sema_sentence = connect_actions(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
 
+  let sema_sentence = parse_clauses(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
+
+  let sema_sentence = cross_check_srl(&sema_sentence, &part, &mut symbol, &mut parse_state)?;
+
+  let sema_sentence = assign_propbank_args(&sema_sentence);
+
   // dbg!(&sema_sentence);
 
   Ok(Some(sema_sentence))
@@ -93,12 +123,17 @@ pub fn connect_actions(
     .actions
     .iter_mut()
   {
+    let verbnet_class = action
+      .verbnet_class
+      .as_deref()
+      .and_then(VerbNetClasses::get);
+
     // Agent / Arg 0
     let positions = parse_state
       .get_symbol_positions(&action.symbol)
       .expect("parse_actions should have created an entry in the parse_state symbols");
 
-    let action_words = positions
+    let mut action_words = positions
       .iter()
       .filter_map(|p| {
         part
@@ -107,7 +142,42 @@ pub fn connect_actions(
       })
       .collect::<Vec<&Word>>();
 
+    // Coordinated verbs share the links of their conjunction, e.g. the subject in
+    // "He stood up and walked out".
+    let conjunctions = action_words
+      .iter()
+      .flat_map(|aw| verb_conjunctions(part, aw))
+      .collect::<Vec<&Word>>();
+
+    action_words.extend(conjunctions);
+
     for aw in action_words.into_iter() {
+      // The subject of a passive verb is its patient, and the object of "by" is its agent:
+      // "the cat was chased by the dog". The subject is linked to the "be" or "get" in front of
+      // the verb, or to the first word of its auxiliary chain, e.g. "will" in "will be eaten".
+      let is_passive = passive_auxiliary(part, aw).is_some();
+
+      if is_passive {
+        let head = auxiliary_chain(part, aw).head;
+
+        if head.has_disjunct(LinkTypes::S, ConnectorPointing::Left) {
+          if let Some(subject) = part
+            .links
+            .find_prev_word_with_link(&head, LinkTypes::S, ConnectorPointing::Right)
+          {
+            coordinated_symbols(part, subject, LinkTypes::SJ, parse_state)
+              .iter()
+              .for_each(|s| {
+                action
+                  .properties
+                  .push(slot_property(verbnet_class, ArgumentSlots::Object, s, |patient| {
+                    ActionProperties::Patient { patient }
+                  }));
+              });
+          }
+        }
+      }
+
       // determine Agent / Arg 0 links
       // Are there any S links (noun to verb): https://www.abisource.com/projects/link-grammar/dict/section-S.html
       if aw.has_disjunct(LinkTypes::S, ConnectorPointing::Left) {
@@ -119,16 +189,15 @@ pub fn connect_actions(
         {
           if word.has_disjunct(LinkTypes::S, ConnectorPointing::Right) {
             // Right Pointing S link exists, which means that the noun has an Arg 0/Agent link
-            // TODO: this is not true in a sentence like "the cat was chased by the dog"
-            parse_state
-              .get_symbols_by_position(word.position)
+            // (passive verbs have their S link on the "be" in front of them, see above)
+            coordinated_symbols(part, word, LinkTypes::SJ, parse_state)
               .iter()
               .for_each(|s| {
                 action
                   .properties
-                  .push(ActionProperties::Agent {
-                    agent: s.to_owned(),
-                  });
+                  .push(slot_property(verbnet_class, ArgumentSlots::Subject, s, |agent| {
+                    ActionProperties::Agent { agent }
+                  }));
               });
 
             break; // only one S link per action for now, but need to handle "p" subscripts
@@ -136,12 +205,46 @@ pub fn connect_actions(
         }
       }
 
+      // The subject of a perfect or progressive verb is linked to the first word of its
+      // auxiliary chain, e.g. "have" in "I have called John" or "should" in "I should have
+      // called John". "I" is linked to "am" and "was" with SX.
+      if !is_passive
+        && !aw.has_disjunct(LinkTypes::S, ConnectorPointing::Left)
+        && !aw.has_disjunct(LinkTypes::I, ConnectorPointing::Left)
+      {
+        let head = auxiliary_chain(part, aw).head;
+
+        let subject_link = [LinkTypes::S, LinkTypes::SX]
+          .into_iter()
+          .find(|link| head.has_disjunct(*link, ConnectorPointing::Left));
+
+        if let Some(link) = subject_link.filter(|_| head.position != aw.position) {
+          if let Some(subject) = part
+            .links
+            .find_prev_word_with_link(&head, link, ConnectorPointing::Right)
+          {
+            coordinated_symbols(part, subject, LinkTypes::SJ, parse_state)
+              .iter()
+              .for_each(|s| {
+                action
+                  .properties
+                  .push(slot_property(verbnet_class, ArgumentSlots::Subject, s, |agent| {
+                    ActionProperties::Agent { agent }
+                  }));
+              });
+          }
+        }
+      }
+
       if aw.has_disjunct(LinkTypes::I, ConnectorPointing::Left) {
         // Left Pointing I means the the verb has an infinitive (will/must/etc) to the left of it.
         // Need to still find the agent in a situation like "I will chase the cat".
+        // Verbs taking an infinitive, like "let" in "let me know", give it its agent in
+        // parse_clauses instead.
         part
           .links
           .find_prev_word_with_link(&aw, LinkTypes::I, ConnectorPointing::Right)
+          .filter(|i| is_infinitive_auxiliary(i))
           .and_then(|i| {
             if i.has_disjunct(LinkTypes::S, ConnectorPointing::Left) {
               part
@@ -152,35 +255,14 @@ pub fn connect_actions(
             }
           })
           .and_then(|s| {
-            parse_state
-              .get_symbols_by_position(s.position)
+            coordinated_symbols(part, s, LinkTypes::SJ, parse_state)
               .iter()
               .for_each(|ss| {
                 action
                   .properties
-                  .push(ActionProperties::Agent {
-                    agent: ss.to_owned(),
-                  });
-              });
-
-            Some(())
-          });
-      }
-
-      if aw.has_disjunct(LinkTypes::IV, ConnectorPointing::Right) {
-        part
-          .links
-          .find_next_word_with_link(&aw, LinkTypes::IV, ConnectorPointing::Left)
-          .and_then(|iv| {
-            parse_state
-              .get_symbols_by_position(iv.position)
-              .iter()
-              .for_each(|s| {
-                action
-                  .properties
-                  .push(ActionProperties::Outcome {
-                    outcome: s.to_owned(),
-                  });
+                  .push(slot_property(verbnet_class, ArgumentSlots::Subject, ss, |agent| {
+                    ActionProperties::Agent { agent }
+                  }));
               });
 
             Some(())
@@ -196,22 +278,24 @@ pub fn connect_actions(
 
         match aw_o_disjuncts.len() {
           1 => {
-            for word in part.links.words[aw.position + 1..].iter() {
-              if word.has_disjunct(LinkTypes::O, ConnectorPointing::Left) {
-                // Left Pointing O link exists, which means that the verb has an Arg 1/Object link
+            // Only the verb's own object, not the objects of verbs after it, e.g. "mom" in
+            // "Remind me to call mom".
+            if let Some(word) = part
+              .links
+              .find_next_word_with_link(aw, LinkTypes::O, ConnectorPointing::Left)
+            {
+              // Left Pointing O link exists, which means that the verb has an Arg 1/Object link
 
-                // if word is a single (has subscript of "s")
-                parse_state
-                  .get_symbols_by_position(word.position)
-                  .iter()
-                  .for_each(|s| {
-                    action
-                      .properties
-                      .push(ActionProperties::Patient {
-                        patient: s.to_owned(),
-                      });
-                  });
-              }
+              // if word is a single (has subscript of "s")
+              coordinated_symbols(part, word, LinkTypes::SJ, parse_state)
+                .iter()
+                .for_each(|s| {
+                  action
+                    .properties
+                    .push(slot_property(verbnet_class, ArgumentSlots::Object, s, |patient| {
+                      ActionProperties::Patient { patient }
+                    }));
+                });
             }
           }
           2 => {
@@ -241,20 +325,6 @@ pub fn connect_actions(
               .find(|w| w.has_disjunct(LinkTypes::O, ConnectorPointing::Left))
               .expect("No first left pointing O link found");
 
-            if first_o.has_raw_disjunct("Ox-") {
-              // first O is a pronoun (me, she, I, it, etc)
-              parse_state
-                .get_symbols_by_position(first_o.position)
-                .iter()
-                .for_each(|s| {
-                  action
-                    .properties
-                    .push(ActionProperties::Benefactive {
-                      benefactive: s.to_owned(),
-                    });
-                });
-            }
-
             let second_o = part
               .links
               .get_next_words_skip(first_o, 1)
@@ -262,32 +332,64 @@ pub fn connect_actions(
               .find(|w| w.has_disjunct(LinkTypes::O, ConnectorPointing::Left))
               .expect("No Second left pointing O link found");
 
-            let second_o_symbols = parse_state.get_symbols_by_position(second_o.position);
+            let second_o_symbols =
+              coordinated_symbols(part, second_o, LinkTypes::SJ, parse_state);
+
+            // Both objects can be the same person, e.g. "my sister" and "Anna" in "I called my
+            // sister Anna".
+            let first_o_symbols = coordinated_symbols(part, first_o, LinkTypes::SJ, parse_state);
+            let same_object = !first_o_symbols.is_empty() && first_o_symbols == second_o_symbols;
+
+            if same_object {
+              second_o_symbols
+                .iter()
+                .for_each(|s| {
+                  action
+                    .properties
+                    .push(slot_property(verbnet_class, ArgumentSlots::Object, s, |patient| {
+                      ActionProperties::Patient { patient }
+                    }));
+                });
+            } else if first_o.has_raw_disjunct("Ox-") {
+              // first O is a pronoun (me, she, I, it, etc)
+              coordinated_symbols(part, first_o, LinkTypes::SJ, parse_state)
+                .iter()
+                .for_each(|s| {
+                  action
+                    .properties
+                    .push(slot_property(
+                      verbnet_class,
+                      ArgumentSlots::IndirectObject,
+                      s,
+                      |benefactive| ActionProperties::Benefactive { benefactive },
+                    ));
+                });
+            }
 
             // TODO: not sure yet how best to handle single/plural/multiple, but the info is encoded in the links.
             // single
-            if second_o.has_raw_disjunct("Os-") {
+            if !same_object && second_o.has_raw_disjunct("Os-") {
               second_o_symbols
                 .iter()
                 .for_each(|s| {
                   action
                     .properties
-                    .push(ActionProperties::Patient {
-                      patient: s.to_owned(),
-                    });
+                    .push(slot_property(verbnet_class, ArgumentSlots::Object, s, |patient| {
+                      ActionProperties::Patient { patient }
+                    }));
                 });
             }
 
             // plural
-            if second_o.has_raw_disjunct("Op-") {
+            if !same_object && second_o.has_raw_disjunct("Op-") {
               second_o_symbols
                 .iter()
                 .for_each(|s| {
                   action
                     .properties
-                    .push(ActionProperties::Patient {
-                      patient: s.to_owned(),
-                    });
+                    .push(slot_property(verbnet_class, ArgumentSlots::Object, s, |patient| {
+                      ActionProperties::Patient { patient }
+                    }));
                 });
             }
 
@@ -307,7 +409,36 @@ pub fn connect_actions(
           .links
           .find_next_word_with_link(&aw, LinkTypes::MV, ConnectorPointing::Left)
         {
-          if mv_right.has_disjunct(LinkTypes::J, ConnectorPointing::Right) {
+          // Coordinated prepositional phrases, e.g. "at home and at work", are joined by MJ links.
+          let mv_rights = if is_conjunction(mv_right) {
+            coordination_cluster(part, mv_right, LinkTypes::MJ)
+          } else {
+            vec![mv_right]
+          };
+
+          // A preposition that also modifies a noun belongs to the noun when the verb's class has
+          // no role for it, e.g. "with" in "I want coffee with no sugar".
+          for mv_right in mv_rights
+            .into_iter()
+            .filter(|w| w.has_disjunct(LinkTypes::J, ConnectorPointing::Right))
+            .filter(|w| {
+              !w.has_disjunct(LinkTypes::M, ConnectorPointing::Left)
+                || verbnet_class
+                  .map(|class| {
+                    class
+                      .role_for_slot(&ArgumentSlots::Prepositional(
+                        w.get_cleaned_word()
+                          .to_lowercase(),
+                      ))
+                      .is_some()
+                  })
+                  .unwrap_or(true)
+            })
+          {
+            let preposition = mv_right
+              .get_cleaned_word()
+              .to_lowercase();
+
             // get recipient
             // For now this is considering a J link to be a recipient, but this could be true for a subset of words, e.g. "on", "for", "over".
             // Also, this might need also refer to a location (e.g. "on the table").
@@ -321,12 +452,35 @@ pub fn connect_actions(
 
               dbg!(&target_symbols);
 
-              for symbol in target_symbols {
+              for symbol in target_symbols.iter() {
+                // not "by Friday"
+                let is_participant = sema_sentence
+                  .agents
+                  .iter()
+                  .any(|a| &a.get_symbol() == symbol)
+                  || sema_sentence
+                    .entities
+                    .iter()
+                    .any(|e| &e.symbol == symbol);
+
+                if is_passive && preposition == "by" && is_participant {
+                  action
+                    .properties
+                    .push(slot_property(verbnet_class, ArgumentSlots::Subject, symbol, |agent| {
+                      ActionProperties::Agent { agent }
+                    }));
+
+                  continue;
+                }
+
                 action
                   .properties
-                  .push(ActionProperties::Recipient {
-                    recipient: symbol.to_owned(),
-                  });
+                  .push(slot_property(
+                    verbnet_class,
+                    ArgumentSlots::Prepositional(preposition.clone()),
+                    symbol,
+                    |recipient| ActionProperties::Recipient { recipient },
+                  ));
               }
             }
           }
@@ -338,6 +492,20 @@ pub fn connect_actions(
   Ok(action_connected_sentence)
 }
 
+// Labels an argument with the thematic role the action's VerbNet class gives its slot.
+// Falls back to the link based guess if the verb has no class, or the class has no role for the slot.
+pub fn slot_property(
+  verbnet_class: Option<&VerbNetClass>,
+  slot: ArgumentSlots,
+  symbol: &str,
+  fallback: impl Fn(String) -> ActionProperties,
+) -> ActionProperties {
+  verbnet_class
+    .and_then(|class| class.role_for_slot(&slot))
+    .and_then(|role| ActionProperties::from_thematic_role(&role, symbol.to_owned()))
+    .unwrap_or_else(|| fallback(symbol.to_owned()))
+}
+
 // gets all words that are targets of a "J" link
 // e.g. "Mary", "Mary and Steve", "Mary, Steve and John"
 pub fn collect_j_target_words<'a>(
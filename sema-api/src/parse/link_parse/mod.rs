@@ -1,10 +1,21 @@
 pub mod link_parse;
 pub mod parse_agents;
+pub mod parse_args;
+pub mod parse_clauses;
+pub mod parse_contacts;
+pub mod parse_coordination;
 pub mod parse_actions;
 pub mod parse_entities;
+pub mod parse_measurements;
+pub mod parse_people;
 pub mod parse_temporal;
 pub mod parse_events;
 pub mod parse_queries;
+pub mod parse_recurrence;
+pub mod parse_modifiers;
+pub mod parse_negation;
+pub mod parse_srl;
+pub mod parse_tense;
 
 pub use link_parse::parse_with_links;
 
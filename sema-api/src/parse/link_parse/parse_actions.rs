@@ -7,14 +7,21 @@ use crate::{
     sema_sentence::SemaSentence,
     symbol::Symbol,
   },
+  propbank::propbank_frames::PropBankFrames,
+  verbnet::verbnet_classes::VerbNetClasses,
 };
 
 use link_parser_rust_bindings::{
-  lp::{disjunct::ConnectorPointing, link_types::LinkTypes},
+  lp::{disjunct::ConnectorPointing, link_types::LinkTypes, word::Word as LPWord},
   pos::POS,
 };
 
-use super::link_parse::ParseState;
+use super::{
+  link_parse::ParseState,
+  parse_modifiers::verb_modifier_properties,
+  parse_negation::verb_is_negated,
+  parse_tense::{is_auxiliary_verb, passive_auxiliary, progressive_auxiliary, verb_tense_properties},
+};
 
 pub fn parse_actions(
   sema_sentence: &SemaSentence,
@@ -24,76 +31,10 @@ pub fn parse_actions(
 ) -> Result<SemaSentence> {
   let mut repaired_sentence = sema_sentence.clone();
 
-  let verbs = part
-    .links
-    .get_verbs();
-
   let mut actions = vec![];
 
-  for v in verbs.into_iter() {
-    // Infinitives can be verbs. Don't know if this is for all cases
-    // Will handle later.
-    if v.has_disjunct(LinkTypes::I, ConnectorPointing::Right) {
-      continue;
-    }
-    // let mut action_type = v.get_cleaned_word();
-    let mut action_type = part.get_word_lemma(&v);
-
-    // Need to use the lemma of the verb if it's past tense.
-    if matches!(v.pos, Some(POS::VerbPastTense | POS::VerbPastTense2)) {
-      action_type = part.get_word_lemma(&v);
-    }
-
-    let mut action = Action::new(action_type, symbol);
-
-    // handle adverbs
-    // if v.has_disjunct(LinkTypes::E, ConnectorPointing::Left) {
-    //   // the verb has some kind of adverb-y thing attached to it.
-    //   if let Some(prev_word) = part.links.get_prev_word(&v) {
-    //     if prev_word.has_raw_disjunct("En+") {
-    //       // the adverb is "not"
-    //       action.properties.push(ActionProperties::Negate { negate: true });
-    //     }
-    //   }
-    // }
-
-    // handle "don't", and "counldn't" style negations
-    if let Some(prev_word) = part
-      .links
-      .get_prev_word(&v)
-    {
-      let tokens = part.get_word_tokens(&prev_word);
-
-      if v.has_disjunct(LinkTypes::E, ConnectorPointing::Left) {
-        // the verb has some kind of adverb-y thing attached to it.
-        if prev_word.has_raw_disjunct("En+") {
-          // the adverb is "not"
-          action
-            .properties
-            .push(ActionProperties::Negate { negate: true });
-        }
-      } else if let Some(last_token) = tokens
-        // Get the last token in the vec. If it's lemma is "not", then it's a negation.
-        .last()
-        .to_owned()
-      {
-        let first_lemma = last_token
-          .word
-          .tags
-          .first()
-          .unwrap()
-          .lemma
-          .as_ref();
-        if first_lemma == "not" {
-          action
-            .properties
-            .push(ActionProperties::Negate { negate: true });
-        }
-      }
-
-      // let lemma = part.get_word_lemma(&prev_word);
-      // dbg!(&lemma);
-    }
+  for v in action_verbs(part).into_iter() {
+    let action = action_from_verb(part, &v, symbol);
 
     parse_state.add_symbol(&action.symbol, vec![v.position]);
 
@@ -108,3 +49,65 @@ pub fn parse_actions(
 
   Ok(repaired_sentence)
 }
+
+// The verbs that should each become an action.
+pub fn action_verbs(part: &SentenceParts) -> Vec<LPWord> {
+  let mut verbs = part
+    .links
+    .get_verbs()
+    .into_iter()
+    .filter(|v| !is_auxiliary_verb(part, v))
+    .collect::<Vec<LPWord>>();
+
+  // Present participles linked as the object of "be", e.g. "working" in "I will be working".
+  verbs.extend(
+    part
+      .links
+      .words
+      .iter()
+      .filter(|w| matches!(w.pos, Some(POS::Gerund)) && progressive_auxiliary(part, w).is_some())
+      .cloned(),
+  );
+
+  verbs.sort_by_key(|v| v.position);
+
+  verbs
+}
+
+// An action for the verb, with its VerbNet class, PropBank roleset, tense, aspect, modality,
+// negation and adverbs, but no arguments.
+pub fn action_from_verb(part: &SentenceParts, v: &LPWord, symbol: &mut Symbol) -> Action {
+  // let mut action_type = v.get_cleaned_word();
+  let mut action_type = part.get_word_lemma(v);
+
+  // Need to use the lemma of the verb if it's past tense.
+  if matches!(v.pos, Some(POS::VerbPastTense | POS::VerbPastTense2)) {
+    action_type = part.get_word_lemma(v);
+  }
+
+  // The subject of a passive verb is its object, e.g. "the cake was eaten".
+  let has_object = v.has_disjunct(LinkTypes::O, ConnectorPointing::Right)
+    || passive_auxiliary(part, v).is_some();
+  let verbnet_class = VerbNetClasses::classify(&action_type, has_object);
+
+  let mut action = Action::new(action_type, symbol);
+  action.verbnet_class = verbnet_class.map(|class| class.id.clone());
+  action.roleset = PropBankFrames::pick_roleset(&action.action_type, action.verbnet_class.as_deref())
+    .map(|roleset| roleset.id.clone());
+
+  action
+    .properties
+    .extend(verb_tense_properties(part, v));
+
+  if verb_is_negated(part, v) {
+    action
+      .properties
+      .push(ActionProperties::Negate { negate: true });
+  }
+
+  action
+    .properties
+    .extend(verb_modifier_properties(part, v));
+
+  action
+}
@@ -1,10 +1,15 @@
 use anyhow::Result;
 
 use crate::{
+  nlp::company_names::{CompanyName, CompanyNames},
   nlp::human_names::HumanNames,
+  nlp::person_names::{is_initial, is_name_suffix, is_surname_particle, is_title, PersonName},
   nlp::sentence_parts::SentenceParts,
   sema::{
-    agents::{Agents, Ego, Subject, Person, PersonProperties},
+    agents::{
+      Agents, Company, CompanyProperties, Ego, Genders, Subject, Person, PersonProperties,
+    },
+    relation::{Kinship, Relations},
     sema_sentence::SemaSentence,
     symbol::Symbol,
   },
@@ -15,12 +20,108 @@ use link_parser_rust_bindings::{
   pos::POS,
 };
 
-use super::link_parse::ParseState;
+use super::{
+  link_parse::ParseState,
+  parse_entities::adjective_modifier,
+  parse_people::{is_person_noun, kin_nouns, name_details, noun_details, PersonDetails},
+  parse_tense::is_imperative_verb,
+};
 
 pub static EGO_WORDS: [&str; 3] = ["i", "my", "me"];
 
 pub static SUBJECT_QUESTION_WORDS: [&str; 1] = ["who"/* , "what", "which" */];
 
+// The first word of many places' names, e.g. "San" in "San Francisco".
+pub static PLACE_PREFIXES: [&str; 11] = [
+  "new",
+  "san",
+  "santa",
+  "los",
+  "las",
+  "saint",
+  "st",
+  "fort",
+  "port",
+  "mount",
+  "lake",
+];
+
+// The last word of a place's or landmark's name, e.g. "Bridge" in "Golden Gate Bridge".
+pub static PLACE_NOUNS: [&str; 20] = [
+  "bridge",
+  "street",
+  "avenue",
+  "road",
+  "boulevard",
+  "square",
+  "park",
+  "river",
+  "lake",
+  "bay",
+  "beach",
+  "island",
+  "mountain",
+  "mountains",
+  "valley",
+  "canyon",
+  "falls",
+  "airport",
+  "station",
+  "city",
+];
+
+// The last word of a company's legal name, e.g. "Corp" in "Acme Corp".
+pub static CORPORATE_SUFFIXES: [&str; 12] = [
+  "inc",
+  "incorporated",
+  "corp",
+  "corporation",
+  "co",
+  "company",
+  "llc",
+  "llp",
+  "ltd",
+  "limited",
+  "plc",
+  "gmbh",
+];
+
+// All caps words that aren't companies, e.g. "OK" or "USA".
+pub static NON_COMPANY_ACRONYMS: [&str; 24] = [
+  "I",
+  "A",
+  "OK",
+  "AM",
+  "PM",
+  "US",
+  "USA",
+  "UK",
+  "EU",
+  "TV",
+  "ID",
+  "PS",
+  "FYI",
+  "ASAP",
+  "BTW",
+  "ETA",
+  "FAQ",
+  "DIY",
+  "VIP",
+  "CEO",
+  "CFO",
+  "CTO",
+  "HR",
+  "IT",
+];
+
+pub static MASCULINE_TITLES: [&str; 3] = ["mr", "mister", "sir"];
+
+pub static FEMININE_TITLES: [&str; 6] = ["mrs", "ms", "miss", "madam", "mme", "mlle"];
+
+pub static MASCULINE_PRONOUNS: [&str; 4] = ["he", "him", "his", "himself"];
+
+pub static FEMININE_PRONOUNS: [&str; 4] = ["she", "her", "hers", "herself"];
+
 pub fn parse_agents(
   sema_sentence: &mut SemaSentence,
   part: &SentenceParts,
@@ -64,7 +165,9 @@ pub fn parse_agents(
     .filter(|lp_word| {
       let w = lp_word.get_cleaned_word().to_lowercase();
       let w_ref = w.as_str();
+      // "who" in "the man who called" is a relative pronoun, not a question
       SUBJECT_QUESTION_WORDS.contains(&w_ref)
+        && !lp_word.has_disjunct(LinkTypes::R, ConnectorPointing::Left)
     })
     .collect::<Vec<_>>();
 
@@ -85,9 +188,253 @@ pub fn parse_agents(
       .push(agent);
   }
 
+  let companies = find_companies(part);
+
   // human names
+  let mut people = vec![];
+  let mut kinships = vec![];
+
+  for name_vec in find_names(part, &companies).iter() {
+    let name_props = name_properties(name_vec);
+    let details = name_details(part, parse_state, name_vec);
+
+    let mut person = Person::new(symbol);
+
+    // The words describing them are theirs too, e.g. "actor" in "the actor Eddie Murphy".
+    let name_positions = name_vec
+      .iter()
+      .map(|lp_word| lp_word.position)
+      .chain(details.positions)
+      .collect::<Vec<_>>();
+
+    parse_state.add_symbol(&person.symbol, name_positions);
+
+    person
+      .properties
+      .extend(name_props);
+
+    person
+      .properties
+      .extend(details.properties);
+
+    person
+      .properties
+      .extend(name_modifiers(part, name_vec));
+
+    if let Some((kinship, relative)) = details.kinship {
+      kinships.push((person.symbol.clone(), kinship, relative));
+    }
+
+    let mut genders = name_genders(part, name_vec);
+    genders.extend(details.genders);
+
+    people.push((person, name_vec[0].position, genders));
+  }
+
+  // relatives without a name, e.g. "my brother"
+  for noun in kin_nouns(part, parse_state) {
+    let mut details = PersonDetails::default();
+    noun_details(part, parse_state, noun, false, &mut details);
+
+    let mut person = Person::new(symbol);
+
+    parse_state.add_symbol(&person.symbol, details.positions);
+
+    person
+      .properties
+      .extend(details.properties);
+
+    if let Some((kinship, relative)) = details.kinship {
+      kinships.push((person.symbol.clone(), kinship, relative));
+    }
+
+    people.push((person, noun.position, details.genders));
+  }
+
+  pronoun_genders(part, &mut people);
+
+  for (mut person, _, genders) in people {
+    if let Some(gender) = combine_genders(&genders) {
+      person
+        .properties
+        .push(PersonProperties::Gender { gender });
+    }
+
+    sema_sentence
+      .agents
+      .push(Agents::Person(person))
+  }
+
+  for (person, kinship, relative) in kinships {
+    sema_sentence
+      .relations
+      .push(Relations::Kinship(Kinship::new(symbol, &person, &kinship, &relative)));
+  }
+
+  // companies
+  for (company_words, company_name) in companies {
+    let mut company = Company::new(symbol);
+
+    let company_positions = company_words
+      .iter()
+      .map(|lp_word| lp_word.position)
+      .collect::<Vec<_>>();
+
+    parse_state.add_symbol(&company.symbol, company_positions);
+
+    company
+      .properties
+      .extend(company_properties(&company_words, company_name));
+
+    sema_sentence
+      .agents
+      .push(Agents::Company(company))
+  }
+
+  Ok(())
+}
+
+// Adjectives in front of a name, e.g. "poor" and "old" in "poor old John".
+pub fn name_modifiers(part: &SentenceParts, name_vec: &[LPWord]) -> Vec<PersonProperties> {
+  let mut modifiers = vec![];
+
+  let mut word = match name_vec.first() {
+    Some(word) => word,
+    None => return modifiers,
+  };
+
+  while let Some(prev_word) = part
+    .links
+    .get_prev_word(word)
+  {
+    if !prev_word.has_pos(POS::Adjective)
+      && !prev_word.has_disjunct(LinkTypes::EA, ConnectorPointing::Right)
+    {
+      break;
+    }
+
+    if let Some((modifier_type, modifier, amplifiers)) = adjective_modifier(prev_word, part) {
+      modifiers.insert(
+        0,
+        PersonProperties::Modifier {
+          modifier_type,
+          modifier: Some(modifier),
+          amplifiers,
+        },
+      );
+    }
+
+    word = prev_word;
+  }
+
+  modifiers
+}
+
+// What a name says about a person's gender:
+// - the link grammar subscript of the first name, e.g. "John.m" or "Jane.f",
+// - how the name data has the first name,
+// - a title in front of the name, e.g. "Mr" or "Mrs".
+pub fn name_genders(part: &SentenceParts, name_vec: &[LPWord]) -> Vec<Genders> {
+  let mut genders = vec![];
+
+  let first = match name_vec
+    .iter()
+    .find(|w| !is_title(&w.get_cleaned_word()))
+  {
+    Some(first) => first,
+    None => return genders,
+  };
+
+  match first.pos {
+    Some(POS::GivenNameMasculine) => genders.push(Genders::Male),
+    Some(POS::GivenNameFeminine) => genders.push(Genders::Female),
+    _ => (),
+  }
+
+  if let Some(gender) = HumanNames::gender(&first.get_cleaned_word()) {
+    genders.push(gender);
+  }
+
+  // The title is usually the start of the name, e.g. "Mrs. Smith"
+  let title = name_vec
+    .first()
+    .filter(|w| is_title(&w.get_cleaned_word()))
+    .or_else(|| {
+      part
+        .links
+        .get_prev_word(first)
+    })
+    .map(|w| {
+      w.get_cleaned_word()
+        .trim_end_matches('.')
+        .to_lowercase()
+    });
+
+  match title.as_deref() {
+    Some(t) if MASCULINE_TITLES.contains(&t) => genders.push(Genders::Male),
+    Some(t) if FEMININE_TITLES.contains(&t) => genders.push(Genders::Female),
+    _ => (),
+  }
+
+  genders
+}
+
+// A gendered pronoun is taken to be about a person named before it, when only one of them can
+// be: "John told Mary she was late" is about Mary, since John is male.
+fn pronoun_genders(part: &SentenceParts, people: &mut [(Person, usize, Vec<Genders>)]) {
+  for word in part
+    .links
+    .words
+    .iter()
+  {
+    let pronoun = word
+      .get_cleaned_word()
+      .to_lowercase();
+
+    let gender = if MASCULINE_PRONOUNS.contains(&pronoun.as_str()) {
+      Genders::Male
+    } else if FEMININE_PRONOUNS.contains(&pronoun.as_str()) {
+      Genders::Female
+    } else {
+      continue;
+    };
+
+    let mut candidates = people
+      .iter_mut()
+      .filter(|(_, position, genders)| {
+        *position < word.position
+          && combine_genders(genders)
+            .map(|g| g == gender)
+            .unwrap_or(true)
+      })
+      .collect::<Vec<_>>();
+
+    if let [(_, _, genders)] = &mut candidates[..] {
+      genders.push(gender);
+    }
+  }
+}
+
+// Unknown when the signals disagree.
+pub fn combine_genders(genders: &[Genders]) -> Option<Genders> {
+  let first = genders.first()?;
+
+  if genders
+    .iter()
+    .all(|g| g == first)
+  {
+    Some(first.clone())
+  } else {
+    Some(Genders::Unknown)
+  }
+}
+
+// Runs of words that look like a person's name, e.g. ["Thomas", "Smith"]. Titles, initials,
+// surname particles and suffixes are part of the run, e.g. ["Dr.", "Martin", "Luther", "King",
+// "Jr."] or ["Ludwig", "van", "Beethoven"].
+pub fn find_names(part: &SentenceParts, companies: &[FoundCompany]) -> Vec<Vec<LPWord>> {
   let mut all_names: Vec<Vec<LPWord>> = vec![];
-  let mut current_name = vec![];
+  let mut current_name: Vec<LPWord> = vec![];
 
   for word in part
     .links
@@ -96,6 +443,18 @@ pub fn parse_agents(
   {
     let cleaned_word = word.get_cleaned_word();
 
+    let has_name = current_name
+      .iter()
+      .any(|w| !is_title(&w.get_cleaned_word()));
+
+    let after_title_or_particle = current_name
+      .last()
+      .map(|w| {
+        let last = w.get_cleaned_word();
+        is_title(&last) || is_surname_particle(&last)
+      })
+      .unwrap_or(false);
+
     if let Some(pos) = word.pos {
       if matches!(
         pos,
@@ -108,75 +467,335 @@ pub fn parse_agents(
     }
 
     if word.word_is_capitalized() {
-      if word.morpho_guessed
-        && HumanNames::contains(&cleaned_word)
-        && !word.has_disjunct(LinkTypes::G, ConnectorPointing::Left) // G connects proper nouns words together
+      if (!has_name && is_title(&cleaned_word))
+        || (word.morpho_guessed
+          && HumanNames::contains(&cleaned_word)
+          && !word.has_disjunct(LinkTypes::G, ConnectorPointing::Left)) // G connects proper nouns words together
+        || after_title_or_particle
+        || (has_name
+          && part
+            .get_word_ducklings(word)
+            .is_empty()
+          && (HumanNames::contains(&cleaned_word)
+            || is_initial(&cleaned_word)
+            || is_name_suffix(&cleaned_word)))
       {
         current_name.push(word.clone());
       }
 
+      continue;
+    } else if has_name && is_surname_particle(&cleaned_word) {
+      current_name.push(word.clone());
+
       continue;
     } else {
-      if current_name.len() > 0 {
-        all_names.push(current_name);
-        current_name = vec![];
+      if let Some(name) = finish_name(current_name) {
+        all_names.push(name);
       }
+
+      current_name = vec![];
     }
   }
 
-  for name_vec in all_names.iter() {
-    let name_props = match name_vec.len() {
-      1 => {
-        let name = name_vec[0]
-          .get_cleaned_word()
-          .to_lowercase();
-        vec![PersonProperties::Name { name }]
-      }
-      2 => {
-        let first_name = name_vec[0]
-          .get_cleaned_word()
-          .to_lowercase();
-        let last_name = name_vec[1]
-          .get_cleaned_word()
-          .to_lowercase();
-        vec![
-          PersonProperties::FirstName { first_name },
-          PersonProperties::LastName { last_name },
-        ]
-      }
-      _ => {
-        let combined_name = name_vec
-          .iter()
-          .map(|w| {
-            w.get_cleaned_word()
-              .to_lowercase()
-          })
-          .collect::<Vec<String>>()
-          .join("_");
-
-        vec![PersonProperties::Name {
-          name: combined_name,
-        }]
-      }
-    };
+  // "Johnson" in "Johnson Inc" is a company, not a person, and "Francisco" in "San Francisco" is
+  // a place.
+  let not_people = companies
+    .iter()
+    .flat_map(|(company_words, _)| company_words)
+    .map(|w| w.position)
+    .chain(place_positions(part))
+    .collect::<Vec<usize>>();
 
-    let mut person = Person::new(symbol);
+  all_names.retain(|name| {
+    !name
+      .iter()
+      .any(|w| not_people.contains(&w.position))
+  });
 
-    let name_positions = name_vec
+  all_names
+}
+
+// A run only names someone if it has more than titles, particles and suffixes. A particle at the
+// end isn't part of it, e.g. "de" in "John de".
+fn finish_name(mut name: Vec<LPWord>) -> Option<Vec<LPWord>> {
+  while name
+    .last()
+    .map(|w| {
+      let last = w.get_cleaned_word();
+      is_title(&last) || (is_surname_particle(&last) && !last.contains('-'))
+    })
+    .unwrap_or(false)
+  {
+    name.pop();
+  }
+
+  let has_name = name
+    .iter()
+    .any(|w| {
+      let w = w.get_cleaned_word();
+      !is_title(&w) && !is_name_suffix(&w) && !is_surname_particle(&w)
+    });
+
+  if has_name {
+    Some(name)
+  } else {
+    None
+  }
+}
+
+// A run of words that names a company, and what the gazetteer knows about it.
+pub type FoundCompany = (Vec<LPWord>, Option<&'static CompanyName>);
+
+// Runs of words that name a company:
+// - names and aliases in the company gazetteer, e.g. "Goldman Sachs" or "IBM",
+// - capitalized words ending with a corporate suffix, e.g. "Acme Corp",
+// - proper nouns G links join together that aren't people's or places' names, e.g. "Blue Origin",
+// - acronyms, e.g. "NASA", unless the whole sentence is in capitals.
+pub fn find_companies(part: &SentenceParts) -> Vec<FoundCompany> {
+  let words = &part
+    .links
+    .words;
+
+  let is_shouted = words
+    .iter()
+    .filter(|w| !w.is_left_wall && !w.is_right_wall)
+    .all(|w| {
+      !w.get_cleaned_word()
+        .chars()
+        .any(|c| c.is_lowercase())
+    });
+
+  let mut companies = vec![];
+  let mut i = 0;
+
+  while i < words.len() {
+    let word = &words[i];
+
+    // Times and other ducks are capitalized too, e.g. "Monday", "PM".
+    // So are commands, e.g. "Target" in "Target the ads".
+    if word.is_left_wall
+      || word.is_right_wall
+      || !word.word_is_capitalized()
+      || is_imperative_verb(word)
+      || !part
+        .get_word_ducklings(word)
+        .is_empty()
+    {
+      i += 1;
+      continue;
+    }
+
+    let lowercase_words = words[i..]
       .iter()
-      .map(|lp_word| lp_word.position)
-      .collect::<Vec<_>>();
+      .map(|w| {
+        w.get_cleaned_word()
+          .to_lowercase()
+      })
+      .collect::<Vec<String>>();
 
-    parse_state.add_symbol(&person.symbol, name_positions);
+    let run_len = words[i..]
+      .iter()
+      .take_while(|w| !w.is_right_wall && w.word_is_capitalized())
+      .count();
 
-    person
-      .properties
-      .extend(name_props);
+    if let Some((len, company_name)) = CompanyNames::longest_match(&lowercase_words) {
+      // "Google Inc"
+      let len = if run_len > len && is_corporate_suffix(&words[i + len]) {
+        len + 1
+      } else {
+        len
+      };
 
-    sema_sentence
-      .agents
-      .push(Agents::Person(person))
+      companies.push((words[i..i + len].to_vec(), Some(company_name)));
+      i += len;
+      continue;
+    }
+
+    let run = &words[i..i + run_len];
+
+    let has_suffix = run.len() > 1
+      && run
+        .last()
+        .map(is_corporate_suffix)
+        .unwrap_or(false);
+
+    // "Dr. House" is a person, and "New York" a place
+    let is_proper_noun_run = run.len() > 1
+      && !is_title(&run[0].get_cleaned_word())
+      && run[1..]
+        .iter()
+        .all(|w| w.has_disjunct(LinkTypes::G, ConnectorPointing::Left))
+      && !run
+        .iter()
+        .any(is_human_name)
+      && !is_place(run);
+
+    let is_acronym = !is_shouted
+      && run
+        .iter()
+        .all(is_acronym);
+
+    if has_suffix || is_proper_noun_run || is_acronym {
+      companies.push((run.to_vec(), None));
+    }
+
+    i += run_len;
   }
 
-  Ok(())
+  companies
+}
+
+pub fn company_properties(
+  company_words: &[LPWord],
+  company_name: Option<&CompanyName>,
+) -> Vec<CompanyProperties> {
+  let name = match company_name {
+    Some(company_name) => company_name
+      .name
+      .to_lowercase()
+      .replace(' ', "_"),
+    None => company_words
+      .iter()
+      .map(|w| {
+        w.get_cleaned_word()
+          .trim_end_matches('.')
+          .to_lowercase()
+      })
+      .collect::<Vec<String>>()
+      .join("_"),
+  };
+
+  let mut properties = vec![CompanyProperties::Name { name }];
+
+  if let Some(industry) = company_name.and_then(|c| c.industry.clone()) {
+    properties.push(CompanyProperties::Industry { industry });
+  }
+
+  properties
+}
+
+fn is_corporate_suffix(word: &LPWord) -> bool {
+  CORPORATE_SUFFIXES.contains(
+    &word
+      .get_cleaned_word()
+      .trim_end_matches('.')
+      .to_lowercase()
+      .as_str(),
+  )
+}
+
+fn is_human_name(word: &LPWord) -> bool {
+  matches!(
+    word.pos,
+    Some(POS::GivenName | POS::GivenNameMasculine | POS::GivenNameFeminine)
+  ) || HumanNames::contains(&word.get_cleaned_word())
+}
+
+// "NASA", but not "I", "OK" or a shouted name like "JOHN".
+fn is_acronym(word: &LPWord) -> bool {
+  let w = word.get_cleaned_word();
+
+  word.all_upper
+    && w.chars().count() > 1
+    && w
+      .chars()
+      .all(|c| c.is_ascii_alphabetic())
+    && !NON_COMPANY_ACRONYMS.contains(&w.as_str())
+    && !is_human_name(word)
+}
+
+// The positions of runs of capitalized words that name a place.
+fn place_positions(part: &SentenceParts) -> Vec<usize> {
+  let words = &part
+    .links
+    .words;
+
+  let mut positions = vec![];
+  let mut i = 0;
+
+  while i < words.len() {
+    let run_len = words[i..]
+      .iter()
+      .take_while(|w| !w.is_left_wall && !w.is_right_wall && w.word_is_capitalized())
+      .count();
+
+    if run_len == 0 {
+      i += 1;
+      continue;
+    }
+
+    let run = &words[i..i + run_len];
+
+    if is_place(run) || is_origin(part, run) {
+      positions.extend(
+        run
+          .iter()
+          .map(|w| w.position),
+      );
+    }
+
+    i += run_len;
+  }
+
+  positions
+}
+
+// Whether a run of capitalized words names a place: the link parser knows it as a location, or
+// it starts or ends like one, e.g. "San Francisco" or "Golden Gate Bridge".
+fn is_place(run: &[LPWord]) -> bool {
+  if run
+    .iter()
+    .any(|w| w.has_pos(POS::Location))
+  {
+    return true;
+  }
+
+  let (first, last) = match (run.first(), run.last()) {
+    (Some(first), Some(last)) if run.len() > 1 => (
+      first
+        .get_cleaned_word()
+        .to_lowercase(),
+      last
+        .get_cleaned_word()
+        .to_lowercase(),
+    ),
+    _ => return false,
+  };
+
+  PLACE_PREFIXES.contains(&first.as_str()) || PLACE_NOUNS.contains(&last.as_str())
+}
+
+// Whether a run of capitalized words is where someone is from, e.g. "Boston" in "a doctor from
+// Boston", even when the link parser takes it for a given name.
+fn is_origin(part: &SentenceParts, run: &[LPWord]) -> bool {
+  let last = match run.last() {
+    Some(last) if last.has_disjunct(LinkTypes::J, ConnectorPointing::Left) => last,
+    _ => return false,
+  };
+
+  part
+    .links
+    .find_prev_word_with_link(last, LinkTypes::J, ConnectorPointing::Right)
+    .filter(|prep| {
+      prep
+        .get_cleaned_word()
+        .eq_ignore_ascii_case("from")
+        && prep.has_disjunct(LinkTypes::M, ConnectorPointing::Left)
+    })
+    .and_then(|prep| {
+      part
+        .links
+        .find_prev_word_with_link(prep, LinkTypes::M, ConnectorPointing::Right)
+    })
+    .map(|head| is_person_noun(head) || is_human_name(head))
+    .unwrap_or(false)
+}
+
+pub fn name_properties(name_vec: &[LPWord]) -> Vec<PersonProperties> {
+  let words = name_vec
+    .iter()
+    .map(|w| w.get_cleaned_word())
+    .collect::<Vec<String>>();
+
+  PersonName::parse(&words).properties()
 }
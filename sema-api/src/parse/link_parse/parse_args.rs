@@ -0,0 +1,206 @@
+use crate::{
+  propbank::propbank_frames::{PropBankFrames, Roleset},
+  sema::{
+    action::{ActionArg, ActionProperties, AdverbCategories, ArgMMods, ArgMods, Args},
+    sema_sentence::SemaSentence,
+  },
+};
+
+// Turns each action's properties into numbered propbank args, using the action's roleset.
+// Roles are matched by VerbNet role link first, then by function tag. Without a matching
+// role, the usual propbank numbering is used (agent = ARG0, patient = ARG1, etc.).
+pub fn assign_propbank_args(sema_sentence: &SemaSentence) -> SemaSentence {
+  let mut args_sentence = sema_sentence.clone();
+
+  for action in args_sentence
+    .actions
+    .iter_mut()
+  {
+    let roleset = action
+      .roleset
+      .as_deref()
+      .and_then(PropBankFrames::get_roleset);
+
+    let mut args: Vec<ActionArg> = vec![];
+
+    for property in action
+      .properties
+      .iter()
+    {
+      let arg = match property_arg(property, roleset, action.verbnet_class.as_deref()) {
+        Some(arg) => arg,
+        None => continue,
+      };
+
+      let is_duplicate = args
+        .iter()
+        .any(|a| a.symbol == arg.symbol && a.arg == arg.arg);
+
+      if !is_duplicate {
+        args.push(arg);
+      }
+    }
+
+    action.args = args;
+  }
+
+  args_sentence
+}
+
+fn property_arg(
+  property: &ActionProperties,
+  roleset: Option<&Roleset>,
+  verbnet_class: Option<&str>,
+) -> Option<ActionArg> {
+  // (filler, verbnet roles, function tags, fallback)
+  let (symbol, verbnet_roles, function_tags, fallback): (Option<&String>, &[&str], &[&str], Args) = match property {
+    ActionProperties::Agent { agent } => (
+      Some(agent),
+      &["agent", "cause", "pivot"],
+      &["PAG"],
+      Args::ARG0(ArgMods::PAG),
+    ),
+    ActionProperties::Experiencer { experiencer } => (
+      Some(experiencer),
+      &["experiencer"],
+      &["PAG"],
+      Args::ARG0(ArgMods::PAG),
+    ),
+    ActionProperties::CoAgent { co_agent } => (
+      Some(co_agent),
+      &["co-agent", "co_agent"],
+      &["COM"],
+      Args::ARGM(ArgMMods::COM),
+    ),
+    ActionProperties::Patient { patient } => (
+      Some(patient),
+      &["patient"],
+      &["PPT"],
+      Args::ARG1(ArgMods::PPT),
+    ),
+    ActionProperties::Theme { theme } => (
+      Some(theme),
+      &["theme", "co-theme", "co_theme"],
+      &["PPT"],
+      Args::ARG1(ArgMods::PPT),
+    ),
+    ActionProperties::Stimulus { stimulus } => (
+      Some(stimulus),
+      &["stimulus"],
+      &["PPT"],
+      Args::ARG1(ArgMods::PPT),
+    ),
+    ActionProperties::Topic { topic } => (
+      Some(topic),
+      &["topic"],
+      &["PPT", "COM"],
+      Args::ARG1(ArgMods::PPT),
+    ),
+    ActionProperties::Proposition { proposition } => (
+      Some(proposition),
+      &["topic", "theme"],
+      &["PPT"],
+      Args::ARG1(ArgMods::PPT),
+    ),
+    ActionProperties::Outcome { outcome } => (
+      Some(outcome),
+      &["product", "result"],
+      &["PPT", "PRD"],
+      Args::ARG1(ArgMods::PPT),
+    ),
+    ActionProperties::Recipient { recipient } => (
+      Some(recipient),
+      &["recipient"],
+      &["GOL"],
+      Args::ARG2(ArgMods::GOL),
+    ),
+    ActionProperties::Benefactive { benefactive } => (
+      Some(benefactive),
+      &["beneficiary"],
+      &["GOL"],
+      Args::ARG2(ArgMods::GOL),
+    ),
+    ActionProperties::Destination { destination } => (
+      Some(destination),
+      &["destination", "goal"],
+      &["GOL"],
+      Args::ARGM(ArgMMods::GOL),
+    ),
+    ActionProperties::Instrument { instrument } => (
+      Some(instrument),
+      &["instrument"],
+      &["MNR"],
+      Args::ARGM(ArgMMods::MNR),
+    ),
+    ActionProperties::Source { source } => (
+      Some(source),
+      &["source", "initial_location", "initial_state", "material"],
+      &["DIR"],
+      Args::ARGM(ArgMMods::DIR),
+    ),
+    ActionProperties::Location { location } => (
+      Some(location),
+      &["location"],
+      &["LOC"],
+      Args::ARGM(ArgMMods::LOC),
+    ),
+    ActionProperties::Attribute { attribute } => (
+      Some(attribute),
+      &["attribute", "asset", "extent", "value"],
+      &["PRD", "VSP", "EXT"],
+      Args::ARG2(ArgMods::PRD),
+    ),
+    ActionProperties::Purpose { purpose } => (Some(purpose), &[], &[], Args::ARGM(ArgMMods::PRP)),
+    ActionProperties::Negate { negate: true } => (None, &[], &[], Args::ARGM(ArgMMods::NEG)),
+    ActionProperties::Modality { .. } => (None, &[], &[], Args::ARGM(ArgMMods::MOD)),
+    ActionProperties::Modifier { modifier_type, .. } => {
+      let modifier_arg = match modifier_type {
+        AdverbCategories::Manner => ArgMMods::MNR,
+        AdverbCategories::Degree => ArgMMods::EXT,
+        AdverbCategories::Frequency => ArgMMods::TMP,
+        AdverbCategories::Certainty => ArgMMods::ADV,
+      };
+
+      (None, &[], &[], Args::ARGM(modifier_arg))
+    }
+    ActionProperties::Negate { negate: false }
+    | ActionProperties::Direction { .. }
+    | ActionProperties::Tense { .. }
+    | ActionProperties::Aspect { .. }
+    | ActionProperties::Voice { .. } => return None,
+  };
+
+  let roleset_arg = roleset.and_then(|roleset| {
+    let numbered_roles = roleset
+      .roles
+      .iter()
+      .filter(|role| !role.n.eq_ignore_ascii_case("m"))
+      .collect::<Vec<_>>();
+
+    verbnet_class
+      .and_then(|verbnet_class| {
+        numbered_roles
+          .iter()
+          .find(|role| {
+            verbnet_roles
+              .iter()
+              .any(|vn_role| role.has_verbnet_role(verbnet_class, vn_role))
+          })
+      })
+      .or_else(|| {
+        function_tags
+          .iter()
+          .find_map(|tag| {
+            numbered_roles
+              .iter()
+              .find(|role| role.f.eq_ignore_ascii_case(tag))
+          })
+      })
+      .map(|role| role.to_arg())
+  });
+
+  Some(ActionArg {
+    arg: roleset_arg.unwrap_or(fallback),
+    symbol: symbol.cloned(),
+  })
+}
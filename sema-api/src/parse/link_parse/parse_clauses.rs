@@ -0,0 +1,426 @@
+use anyhow::Result;
+
+use link_parser_rust_bindings::lp::{
+  disjunct::ConnectorPointing, link_types::LinkTypes, word::Word,
+};
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  sema::{
+    action::ActionProperties,
+    agents::{Agents, CompanyProperties, PersonProperties},
+    entity::EntityProperties,
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+  verbnet::verbnet_classes::{ArgumentSlots, VerbNetClasses},
+};
+
+use super::{
+  link_parse::{slot_property, ParseState},
+  parse_coordination::coordinated_symbols,
+  parse_tense::auxiliary_chain,
+};
+
+// Clauses inside the sentence, which would otherwise be free floating actions:
+// - A relative clause restricts the entity or agent it modifies, and the noun fills the gap in
+//   the clause. B links the noun to the clause's verb, and RS links a relative pronoun to the
+//   verb when the noun is the clause's subject: "the dog that chased me" is the dog that did the
+//   chasing, and "the invoice that John sent" is the invoice John sent.
+// - A clausal complement is the proposition of the verb it's embedded in. CV links the clause's
+//   verb back to that verb, or to the "that" in front of the clause: "He said (that) he was sorry".
+// - An infinitive is the theme of the verb that takes it, or the purpose of the verb it follows,
+//   and its agent is that verb's subject or object, see infinitive_controller.
+pub fn parse_clauses(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  _symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+) -> Result<SemaSentence> {
+  let mut output_sentence = sema_sentence.clone();
+
+  // Each action's symbol and the first word of its verb's auxiliary chain, which has the links
+  // to the rest of the sentence, e.g. "was" in "the dog that was chasing me".
+  let action_heads = output_sentence
+    .actions
+    .iter()
+    .filter_map(|action| {
+      let position = parse_state
+        .get_symbol_positions(&action.symbol)?
+        .first()?;
+
+      let verb = part
+        .links
+        .get_word_by_position(*position)?;
+
+      Some((
+        action
+          .symbol
+          .clone(),
+        verb.position,
+        auxiliary_chain(part, verb).head,
+      ))
+    })
+    .collect::<Vec<(String, usize, Word)>>();
+
+  for (action_symbol, verb_position, head) in &action_heads {
+    // relative clauses
+    let complement_nouns = complement_noun(part, *verb_position, head)
+      .map(|noun| participant_symbols(&output_sentence, parse_state, noun.position))
+      .unwrap_or_default();
+
+    if !complement_nouns.is_empty() {
+      for noun_symbol in &complement_nouns {
+        restrict(&mut output_sentence, noun_symbol, action_symbol);
+        fill_gap(&mut output_sentence, action_symbol, noun_symbol, false);
+      }
+
+      continue;
+    }
+
+    if head.has_disjunct(LinkTypes::B, ConnectorPointing::Left) {
+      if let Some(noun) = part
+        .links
+        .find_prev_word_with_link(head, LinkTypes::B, ConnectorPointing::Right)
+      {
+        let is_subject = head.has_disjunct(LinkTypes::RS, ConnectorPointing::Left);
+
+        for noun_symbol in participant_symbols(&output_sentence, parse_state, noun.position) {
+          restrict(&mut output_sentence, &noun_symbol, action_symbol);
+          fill_gap(
+            &mut output_sentence,
+            action_symbol,
+            &noun_symbol,
+            is_subject,
+          );
+        }
+      }
+    }
+
+    // clausal complements
+    if let Some(clause_verb) = complement_verb(part, *verb_position, head) {
+      let embedder = part
+        .links
+        .find_prev_word_with_link(&clause_verb, LinkTypes::CV, ConnectorPointing::Right)
+        .and_then(|w| {
+          // "He said THAT he was sorry"
+          if w.has_disjunct(LinkTypes::TH, ConnectorPointing::Left) {
+            part
+              .links
+              .find_prev_word_with_link(w, LinkTypes::TH, ConnectorPointing::Right)
+          } else {
+            Some(w)
+          }
+        });
+
+      // Subordinating conjunctions, like "when" in "He left when he saw me", aren't actions.
+      let embedding_action =
+        embedder.and_then(|embedder| action_at(&action_heads, embedder, action_symbol));
+
+      if let Some(embedding_symbol) = embedding_action {
+        add_action_property(
+          &mut output_sentence,
+          &embedding_symbol,
+          ActionProperties::Proposition {
+            proposition: action_symbol.clone(),
+          },
+        );
+      }
+    }
+
+    // infinitives
+    if head.has_disjunct(LinkTypes::I, ConnectorPointing::Left) {
+      let controller = part
+        .links
+        .find_prev_word_with_link(head, LinkTypes::I, ConnectorPointing::Right)
+        .and_then(|marker| infinitive_controller(part, marker));
+
+      if let Some(controller) = controller {
+        if let Some(controlling_symbol) = action_at(&action_heads, controller.verb, action_symbol) {
+          let property = if controller.is_purpose {
+            ActionProperties::Purpose {
+              purpose: action_symbol.clone(),
+            }
+          } else {
+            ActionProperties::Theme {
+              theme: action_symbol.clone(),
+            }
+          };
+
+          add_action_property(&mut output_sentence, &controlling_symbol, property);
+        }
+
+        for agent in controlled_agents(part, parse_state, &controller) {
+          fill_gap(&mut output_sentence, action_symbol, &agent, true);
+        }
+      }
+    }
+  }
+
+  Ok(output_sentence)
+}
+
+// The link parser can read a relative clause missing its object as a complement of the verb
+// before it, e.g. "the invoice that John sent" in "remind me about the invoice that John sent".
+// It's the noun's when "that" follows the object of a preposition and the clause's verb has no
+// object. After a verb's own object it's a complement: "I told John that I was leaving".
+fn complement_noun<'a>(
+  part: &'a SentenceParts,
+  verb_position: usize,
+  head: &Word,
+) -> Option<&'a Word> {
+  let clause_verb = complement_verb(part, verb_position, head)?;
+
+  let that = part
+    .links
+    .find_prev_word_with_link(&clause_verb, LinkTypes::CV, ConnectorPointing::Right)?;
+
+  let is_that = that
+    .get_cleaned_word()
+    .eq_ignore_ascii_case("that")
+    && that.has_disjunct(LinkTypes::TH, ConnectorPointing::Left);
+
+  let verb = part
+    .links
+    .get_word_by_position(verb_position)?;
+
+  if !is_that
+    || verb.has_disjunct(LinkTypes::O, ConnectorPointing::Right)
+    || verb.has_disjunct(LinkTypes::P, ConnectorPointing::Right)
+  {
+    return None;
+  }
+
+  part
+    .links
+    .get_prev_word(that)
+    .filter(|noun| noun.has_disjunct(LinkTypes::J, ConnectorPointing::Left))
+}
+
+// The word of a clause CV links back to, which is its first auxiliary, "was" in "He said he was
+// sorry", or after a modal its verb, "call" in "He said he would call me".
+fn complement_verb(part: &SentenceParts, verb_position: usize, head: &Word) -> Option<Word> {
+  if head.has_disjunct(LinkTypes::CV, ConnectorPointing::Left) {
+    return Some(head.clone());
+  }
+
+  part
+    .links
+    .get_word_by_position(verb_position)
+    .filter(|verb| verb.has_disjunct(LinkTypes::CV, ConnectorPointing::Left))
+    .cloned()
+}
+
+// The verb an infinitive depends on, given the word in front of the infinitive:
+// - "to" after a verb taking an infinitive (TO): "I want to buy a car",
+// - "to" starting a purpose clause (MVi): "I went to the store to buy milk",
+// - a verb taking a bare infinitive (I): "Let me know", "Help me move the couch".
+struct InfinitiveController<'a> {
+  verb: &'a Word,
+  is_purpose: bool,
+  // Whose action the infinitive is, e.g. "me" in "Remind me to call John" (TOo) or
+  // "Let me know". Otherwise it's the verb's subject's: "I want to buy a car".
+  is_object_control: bool,
+}
+
+fn infinitive_controller<'a>(
+  part: &'a SentenceParts,
+  marker: &'a Word,
+) -> Option<InfinitiveController<'a>> {
+  let is_to = marker
+    .get_cleaned_word()
+    .eq_ignore_ascii_case("to");
+
+  if !is_to {
+    return Some(InfinitiveController {
+      verb: marker,
+      is_purpose: false,
+      is_object_control: marker.has_disjunct(LinkTypes::O, ConnectorPointing::Right),
+    });
+  }
+
+  if marker.has_disjunct(LinkTypes::TO, ConnectorPointing::Left) {
+    let verb = part
+      .links
+      .find_prev_word_with_link(marker, LinkTypes::TO, ConnectorPointing::Right)?;
+
+    let is_object_control = verb.has_disjunct(LinkTypes::O, ConnectorPointing::Right)
+      && verb
+        .get_disjuncts(LinkTypes::TO)
+        .iter()
+        .any(|d| d.pointing == ConnectorPointing::Right && d.has_subscript(vec!["o"]));
+
+    return Some(InfinitiveController {
+      verb,
+      is_purpose: false,
+      is_object_control,
+    });
+  }
+
+  let is_purpose = marker
+    .get_disjuncts(LinkTypes::MV)
+    .iter()
+    .any(|d| d.pointing == ConnectorPointing::Left && d.has_subscript(vec!["i"]));
+
+  if !is_purpose {
+    return None;
+  }
+
+  part
+    .links
+    .find_prev_word_with_link(marker, LinkTypes::MV, ConnectorPointing::Right)
+    .map(|verb| InfinitiveController {
+      verb,
+      is_purpose,
+      is_object_control: false,
+    })
+}
+
+// The symbols of whoever does the infinitive's action.
+fn controlled_agents(
+  part: &SentenceParts,
+  parse_state: &ParseState,
+  controller: &InfinitiveController,
+) -> Vec<String> {
+  if controller.is_object_control {
+    if let Some(object) = part
+      .links
+      .find_next_word_with_link(controller.verb, LinkTypes::O, ConnectorPointing::Left)
+    {
+      return coordinated_symbols(part, object, LinkTypes::SJ, parse_state);
+    }
+  }
+
+  subject_symbols(part, parse_state, controller.verb)
+}
+
+// The subject of a verb, which may be an infinitive itself: "I" in "I want to try to buy a car".
+// Imperatives have none.
+fn subject_symbols(part: &SentenceParts, parse_state: &ParseState, verb: &Word) -> Vec<String> {
+  let head = auxiliary_chain(part, verb).head;
+
+  if head.has_disjunct(LinkTypes::S, ConnectorPointing::Left) {
+    return part
+      .links
+      .find_prev_word_with_link(&head, LinkTypes::S, ConnectorPointing::Right)
+      .map(|subject| coordinated_symbols(part, subject, LinkTypes::SJ, parse_state))
+      .unwrap_or_default();
+  }
+
+  if head.has_disjunct(LinkTypes::I, ConnectorPointing::Left) {
+    return part
+      .links
+      .find_prev_word_with_link(&head, LinkTypes::I, ConnectorPointing::Right)
+      .and_then(|marker| infinitive_controller(part, marker))
+      .map(|controller| controlled_agents(part, parse_state, &controller))
+      .unwrap_or_default();
+  }
+
+  vec![]
+}
+
+// The symbol of the action whose verb, or first auxiliary, is the word.
+fn action_at(action_heads: &[(String, usize, Word)], word: &Word, except: &str) -> Option<String> {
+  action_heads
+    .iter()
+    .find(|(s, position, h)| {
+      s != except && (*position == word.position || h.position == word.position)
+    })
+    .map(|(s, _, _)| s.clone())
+}
+
+fn add_action_property(sentence: &mut SemaSentence, symbol: &str, property: ActionProperties) {
+  if let Some(action) = sentence
+    .actions
+    .iter_mut()
+    .find(|a| a.symbol == symbol)
+  {
+    action
+      .properties
+      .push(property);
+  }
+}
+
+// The symbols of the entities and agents at a position.
+fn participant_symbols(
+  sentence: &SemaSentence,
+  parse_state: &ParseState,
+  position: usize,
+) -> Vec<String> {
+  parse_state
+    .get_symbols_by_position(position)
+    .into_iter()
+    .filter(|s| {
+      sentence
+        .entities
+        .iter()
+        .any(|e| &e.symbol == s)
+        || sentence
+          .agents
+          .iter()
+          .any(|a| &a.get_symbol() == s)
+    })
+    .collect()
+}
+
+fn restrict(sentence: &mut SemaSentence, symbol: &str, action_symbol: &str) {
+  let restriction = action_symbol.to_string();
+
+  if let Some(entity) = sentence
+    .entities
+    .iter_mut()
+    .find(|e| e.symbol == symbol)
+  {
+    entity
+      .properties
+      .push(EntityProperties::Restriction { restriction });
+
+    return;
+  }
+
+  match sentence
+    .agents
+    .iter_mut()
+    .find(|a| a.get_symbol() == symbol)
+  {
+    Some(Agents::Person(person)) => person
+      .properties
+      .push(PersonProperties::Restriction { restriction }),
+    Some(Agents::Company(company)) => company
+      .properties
+      .push(CompanyProperties::Restriction { restriction }),
+    _ => (),
+  }
+}
+
+// The noun is the subject of the clause, "the dog that chased me", or its object, "the invoice
+// that John sent". Infinitives get their subject the same way.
+fn fill_gap(sentence: &mut SemaSentence, action_symbol: &str, symbol: &str, is_subject: bool) {
+  let action = match sentence
+    .actions
+    .iter_mut()
+    .find(|a| a.symbol == action_symbol)
+  {
+    Some(action) => action,
+    None => return,
+  };
+
+  let verbnet_class = action
+    .verbnet_class
+    .as_deref()
+    .and_then(VerbNetClasses::get);
+
+  let property = if is_subject {
+    slot_property(verbnet_class, ArgumentSlots::Subject, symbol, |agent| {
+      ActionProperties::Agent { agent }
+    })
+  } else {
+    slot_property(verbnet_class, ArgumentSlots::Object, symbol, |patient| {
+      ActionProperties::Patient { patient }
+    })
+  };
+
+  action
+    .properties
+    .push(property);
+}
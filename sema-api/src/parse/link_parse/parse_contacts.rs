@@ -0,0 +1,278 @@
+use anyhow::Result;
+
+use link_parser_rust_bindings::lp::{
+  disjunct::ConnectorPointing, link_types::LinkTypes, word::Word,
+};
+
+use crate::{
+  nlp::{duck::DuckValues, sentence_parts::SentenceParts},
+  sema::{
+    agents::{Agents, Company},
+    contact::{is_email, is_url, Contact, ContactProperties},
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+};
+
+use super::{link_parse::ParseState, parse_agents::company_properties};
+
+// Duckling dimensions that are contact details. Emails come first, so the domain of an email
+// isn't also taken as a url.
+pub static CONTACT_DIMS: [&str; 3] = ["email", "phone-number", "url"];
+
+// Prepositions that introduce contact details, e.g. "call John at 555-1234".
+pub static CONTACT_PREPOSITIONS: [&str; 4] = ["at", "on", "via", "through"];
+
+// Emails, phone numbers and urls, attached to the person or company they belong to:
+// "call John at 555-1234", "email Acme at sales@acme.com", "John at john@acme.com".
+// Ones that don't belong to an agent go in the sentence's contacts.
+pub fn parse_contacts(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+) -> Result<SemaSentence> {
+  let mut output_sentence = sema_sentence.clone();
+  let mut contact_positions: Vec<usize> = vec![];
+
+  // contact ducks
+  for dim in CONTACT_DIMS {
+    for p in part
+      .duck
+      .parts
+      .iter()
+      .filter(|p| p.dim == dim)
+    {
+      let value = match &p.value {
+        DuckValues::Value { grain: _, value } => value,
+        _ => continue,
+      };
+
+      let positions = part.get_duck_word_positions(p);
+
+      if positions.is_empty()
+        || positions
+          .iter()
+          .any(|p| contact_positions.contains(p))
+      {
+        continue;
+      }
+
+      let contact = match dim {
+        "email" => ContactProperties::email(value),
+        "phone-number" => ContactProperties::phone_number(value),
+        _ => ContactProperties::url(value),
+      };
+
+      contact_positions.extend(&positions);
+
+      attach_contact(&mut output_sentence, part, symbol, parse_state, contact, positions);
+    }
+  }
+
+  // Emails and urls the link parser kept as one word, that duckling didn't find.
+  for word in &part
+    .links
+    .words
+  {
+    if contact_positions.contains(&word.position) {
+      continue;
+    }
+
+    let text = word.get_cleaned_word();
+
+    let contact = if is_email(&text) {
+      ContactProperties::email(&text)
+    } else if is_url(&text) {
+      ContactProperties::url(&text)
+    } else {
+      continue;
+    };
+
+    contact_positions.push(word.position);
+
+    attach_contact(
+      &mut output_sentence,
+      part,
+      symbol,
+      parse_state,
+      contact,
+      vec![word.position],
+    );
+  }
+
+  Ok(output_sentence)
+}
+
+fn attach_contact(
+  sentence: &mut SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+  contact: ContactProperties,
+  positions: Vec<usize>,
+) {
+  // Contact details aren't entities, e.g. an email the link parser guessed is a noun.
+  let contact_symbols = sentence
+    .entities
+    .iter()
+    .map(|e| e.get_symbol())
+    .filter(|s| {
+      parse_state
+        .get_symbol_positions(s)
+        .map(|ps| {
+          ps.iter()
+            .all(|p| positions.contains(p))
+        })
+        .unwrap_or(false)
+    })
+    .collect::<Vec<String>>();
+
+  sentence
+    .entities
+    .retain(|e| !contact_symbols.contains(&e.symbol));
+
+  for contact_symbol in &contact_symbols {
+    parse_state.remove_symbol(contact_symbol);
+  }
+
+  let owner_word = contact_owner(part, &positions);
+
+  let owner = owner_word
+    .and_then(|owner| agent_at(sentence, parse_state, owner.position))
+    .or_else(|| {
+      owner_word.and_then(|owner| domain_company(sentence, symbol, parse_state, owner, &contact))
+    });
+
+  match owner.and_then(|i| sentence.agents.get_mut(i)) {
+    Some(Agents::Person(person)) => person
+      .properties
+      .push(contact.into()),
+    Some(Agents::Company(company)) => company
+      .properties
+      .push(contact.into()),
+    _ => {
+      let contact = Contact::new(symbol, contact);
+
+      parse_state.add_symbol(&contact.symbol, positions);
+
+      sentence
+        .contacts
+        .push(contact);
+    }
+  }
+}
+
+// The word for who contact details belong to, through the preposition in front of them:
+// - M links a preposition to the noun it modifies: "John at 555-1234".
+// - MV links a preposition to its verb, and the verb's object is the owner: "call John at ...".
+fn contact_owner<'a>(part: &'a SentenceParts, positions: &[usize]) -> Option<&'a Word> {
+  let first = *positions
+    .iter()
+    .min()?;
+
+  let preposition = part
+    .links
+    .get_word_by_position(first.checked_sub(1)?)
+    .filter(|w| {
+      CONTACT_PREPOSITIONS.contains(
+        &w.get_cleaned_word()
+          .to_lowercase()
+          .as_str(),
+      )
+    })?;
+
+  if preposition.has_disjunct(LinkTypes::M, ConnectorPointing::Left) {
+    return part
+      .links
+      .find_prev_word_with_link(preposition, LinkTypes::M, ConnectorPointing::Right);
+  }
+
+  if preposition.has_disjunct(LinkTypes::MV, ConnectorPointing::Left) {
+    let verb = part
+      .links
+      .find_prev_word_with_link(preposition, LinkTypes::MV, ConnectorPointing::Right)?;
+
+    return part
+      .links
+      .get_next_words(verb)
+      .into_iter()
+      .filter(|w| w.position < preposition.position)
+      .find(|w| w.has_disjunct(LinkTypes::O, ConnectorPointing::Left));
+  }
+
+  None
+}
+
+// A name the link parser didn't know is a company when the domain of its email or url is named
+// after it, e.g. "Acme" in "email Acme at sales@acme.com". It was taken for an entity, so it
+// becomes a company agent instead.
+fn domain_company(
+  sentence: &mut SemaSentence,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+  owner: &Word,
+  contact: &ContactProperties,
+) -> Option<usize> {
+  let name = owner
+    .get_cleaned_word()
+    .to_lowercase();
+
+  let is_domain_name = contact
+    .domain()
+    .and_then(|domain| {
+      domain
+        .split('.')
+        .next()
+    })
+    .map(|label| label == name)
+    .unwrap_or(false);
+
+  if !owner.word_is_capitalized() || !is_domain_name {
+    return None;
+  }
+
+  let owner_symbols = parse_state.get_symbols_by_position(owner.position);
+
+  let entity_symbols = sentence
+    .entities
+    .iter()
+    .map(|e| e.get_symbol())
+    .filter(|s| owner_symbols.contains(s))
+    .collect::<Vec<String>>();
+
+  sentence
+    .entities
+    .retain(|e| !entity_symbols.contains(&e.symbol));
+
+  for entity_symbol in &entity_symbols {
+    parse_state.remove_symbol(entity_symbol);
+  }
+
+  let mut company = Company::new(symbol);
+
+  company
+    .properties
+    .extend(company_properties(std::slice::from_ref(owner), None));
+
+  parse_state.add_symbol(&company.symbol, vec![owner.position]);
+
+  sentence
+    .agents
+    .push(Agents::Company(company));
+
+  Some(sentence.agents.len() - 1)
+}
+
+// The person or company whose words include a position.
+fn agent_at(sentence: &SemaSentence, parse_state: &ParseState, position: usize) -> Option<usize> {
+  let symbols = parse_state.get_symbols_by_position(position);
+
+  sentence
+    .agents
+    .iter()
+    .position(|agent| {
+      matches!(agent, Agents::Person(_) | Agents::Company(_))
+        && symbols.contains(&agent.get_symbol())
+    })
+}
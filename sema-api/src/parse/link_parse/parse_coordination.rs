@@ -0,0 +1,269 @@
+use anyhow::Result;
+
+use link_parser_rust_bindings::lp::{
+  disjunct::ConnectorPointing, link_types::LinkTypes, word::Word,
+};
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  sema::{
+    agents::{Agents, Group, GroupProperties},
+    entity::{Entity, EntityProperties},
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+};
+
+use super::link_parse::ParseState;
+
+pub static CONJUNCTIONS: [&str; 6] = ["and", "or", "nor", "but", "&", ","];
+
+// Conjunctions whose conjuncts are together, rather than alternatives like "or".
+pub static COLLECTIVE_CONJUNCTIONS: [&str; 2] = ["and", "&"];
+
+// Coordinated people become a group agent, and coordinated entities a "list" entity, with the
+// conjuncts as members: "John and Mary baked a cake", "buy eggs, milk and bread".
+// The actions still point at each member.
+pub fn parse_coordination(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+) -> Result<SemaSentence> {
+  let mut output_sentence = sema_sentence.clone();
+  let mut coordinated_positions: Vec<usize> = vec![];
+
+  for word in part
+    .links
+    .words
+    .iter()
+    .filter(|w| {
+      is_conjunction(w)
+        && !w
+          .get_disjuncts(LinkTypes::SJ)
+          .is_empty()
+    })
+  {
+    if coordinated_positions.contains(&word.position) {
+      continue;
+    }
+
+    let cluster = coordination_cluster(part, word, LinkTypes::SJ);
+
+    coordinated_positions.extend(
+      cluster
+        .iter()
+        .map(|w| w.position),
+    );
+
+    let is_collective = cluster
+      .iter()
+      .filter(|w| is_conjunction(w))
+      .any(|w| {
+        COLLECTIVE_CONJUNCTIONS.contains(
+          &w.get_cleaned_word()
+            .to_lowercase()
+            .as_str(),
+        )
+      });
+
+    if !is_collective {
+      continue;
+    }
+
+    let members = conjunct_symbols(&cluster, parse_state);
+
+    add_coordination(&mut output_sentence, members, symbol);
+  }
+
+  Ok(output_sentence)
+}
+
+// A group for coordinated agents, or a list for coordinated entities. Mixed coordinations, like
+// "John and the car", get neither. Returns the new symbol.
+pub fn add_coordination(
+  sentence: &mut SemaSentence,
+  members: Vec<String>,
+  symbol: &mut Symbol,
+) -> Option<String> {
+  if members.len() < 2 {
+    return None;
+  }
+
+  let all_agents = members.iter().all(|m| {
+    sentence
+      .agents
+      .iter()
+      .any(|a| &a.get_symbol() == m)
+  });
+
+  let all_entities = members.iter().all(|m| {
+    sentence
+      .entities
+      .iter()
+      .any(|e| &e.symbol == m)
+  });
+
+  if all_agents {
+    let mut group = Group::new(symbol);
+    let s = group
+      .symbol
+      .clone();
+
+    group
+      .properties
+      .push(GroupProperties::Members { members });
+
+    sentence
+      .agents
+      .push(Agents::Group(group));
+
+    Some(s)
+  } else if all_entities {
+    let mut list = Entity::new("list".to_string(), symbol);
+    let s = list.get_symbol();
+
+    list
+      .properties
+      .push(EntityProperties::Members { members });
+
+    sentence
+      .entities
+      .push(list);
+
+    Some(s)
+  } else {
+    None
+  }
+}
+
+// The symbols of a word and of the words coordinated with it, e.g. both people for the "and" in
+// "John and Mary baked a cake". A word that isn't coordinated only gives its own symbols.
+pub fn coordinated_symbols(
+  part: &SentenceParts,
+  word: &Word,
+  link_type: LinkTypes,
+  parse_state: &ParseState,
+) -> Vec<String> {
+  let mut symbols = parse_state.get_symbols_by_position(word.position);
+
+  if !is_conjunction(word) {
+    return symbols;
+  }
+
+  for s in conjunct_symbols(&coordination_cluster(part, word, link_type), parse_state) {
+    if !symbols.contains(&s) {
+      symbols.push(s);
+    }
+  }
+
+  symbols
+}
+
+// The conjunctions coordinating a verb with others. They carry the links the verbs share, e.g.
+// the subject in "He stood up and walked out", or the object in "I washed and dried the dishes".
+pub fn verb_conjunctions<'a>(part: &'a SentenceParts, verb: &Word) -> Vec<&'a Word> {
+  if verb
+    .get_disjuncts(LinkTypes::VJ)
+    .is_empty()
+  {
+    return vec![];
+  }
+
+  coordination_cluster(part, verb, LinkTypes::VJ)
+    .into_iter()
+    .filter(|w| is_conjunction(w))
+    .collect()
+}
+
+// The words joined to a word by a coordination link (SJ for nouns, VJ for verbs, MJ for
+// prepositional phrases, AJ for adjectives), conjunctions included, in sentence order.
+// A word's links are matched to the nearest word with the opposite connector, which is how
+// lists like "eggs, milk and bread" are linked.
+pub fn coordination_cluster<'a>(
+  part: &'a SentenceParts,
+  word: &Word,
+  link_type: LinkTypes,
+) -> Vec<&'a Word> {
+  let words = &part
+    .links
+    .words;
+
+  let mut positions = vec![word.position];
+  let mut i = 0;
+
+  while i < positions.len() {
+    let current = match part
+      .links
+      .get_word_by_position(positions[i])
+    {
+      Some(current) => current,
+      None => break,
+    };
+
+    let next = if current.has_disjunct(link_type, ConnectorPointing::Right) {
+      words[current.position + 1..]
+        .iter()
+        .find(|w| w.has_disjunct(link_type, ConnectorPointing::Left))
+    } else {
+      None
+    };
+
+    let prev = if current.has_disjunct(link_type, ConnectorPointing::Left) {
+      words[..current.position]
+        .iter()
+        .rev()
+        .find(|w| w.has_disjunct(link_type, ConnectorPointing::Right))
+    } else {
+      None
+    };
+
+    for w in next
+      .into_iter()
+      .chain(prev)
+    {
+      if !positions.contains(&w.position) {
+        positions.push(w.position);
+      }
+    }
+
+    i += 1;
+  }
+
+  positions.sort();
+
+  positions
+    .into_iter()
+    .filter_map(|p| {
+      part
+        .links
+        .get_word_by_position(p)
+    })
+    .collect()
+}
+
+pub fn is_conjunction(word: &Word) -> bool {
+  CONJUNCTIONS.contains(
+    &word
+      .get_cleaned_word()
+      .to_lowercase()
+      .as_str(),
+  )
+}
+
+fn conjunct_symbols(cluster: &[&Word], parse_state: &ParseState) -> Vec<String> {
+  let mut symbols: Vec<String> = vec![];
+
+  for w in cluster
+    .iter()
+    .filter(|w| !is_conjunction(w))
+  {
+    for s in parse_state.get_symbols_by_position(w.position) {
+      if !symbols.contains(&s) {
+        symbols.push(s);
+      }
+    }
+  }
+
+  symbols
+}
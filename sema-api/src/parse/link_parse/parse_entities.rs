@@ -9,15 +9,21 @@ use link_parser_rust_bindings::{
 use crate::{
   nlp::sentence_parts::SentenceParts,
   sema::{
+    agents::Agents,
     entity::{Entity, EntityProperties, Quantities},
     sema_sentence::SemaSentence,
     symbol::Symbol,
   },
 };
 
-use super::link_parse::ParseState;
+use super::{
+  link_parse::ParseState,
+  parse_measurements::is_unit_measurement,
+  parse_negation::{is_absence_preposition, is_negative_determiner},
+};
 // use crate::parse::link_parse::parse_temporal::TIME_NOUNS;
 use crate::parse::numbers::*;
+use crate::adjectives::adjective_taxonomy::AdjectiveTaxonomy;
 use crate::wordnet::wordnet_noun_objects::{Tree, WORDNET_NOUN_OBJECTS};
 
 pub fn build_noun_phrases(
@@ -91,7 +97,9 @@ pub fn build_noun_phrases(
         .to_lowercase();
       // println!("lemma 2: {:?}, word.position: {:?}", &lemma, word.position);
 
-      if branch.contains_key(&lemma) {
+      // Determiners are only in the middle of a phrase, e.g. "the" in "hair of the dog", so one in
+      // front of a noun is never part of it.
+      if branch.contains_key(&lemma) && !word.has_disjunct(LinkTypes::D, ConnectorPointing::Right) {
         let tree_node = branch
           .get(&lemma)
           .unwrap();
@@ -99,8 +107,14 @@ pub fn build_noun_phrases(
         // println!("noun_phrase.push 2");
         noun_phrase.push(word);
         build_noun_phrases(words, part, &tree_node.branches, noun_phrase, noun_phrases)?;
-      } else if word.has_disjunct(LinkTypes::AN, ConnectorPointing::Right) {
+      } else if word.has_disjunct(LinkTypes::AN, ConnectorPointing::Right)
+        && !AdjectiveTaxonomy::contains(&lemma)
+        && !is_unit_measurement(&word, part)
+      {
         // if word branches aren't in dictionary, might still be a noun phrase.
+        // Nouns in the adjective taxonomy, like "cotton", are modifiers instead.
+        // Units with a number, like "kg" in "5 kg bag", are measurements, but without one they're
+        // part of the compound, like "stone" in "stone wall".
         // Look to see if word is a noun with an "AN" disjunct.
         // "AN" connects noun-modifiers to following nouns: "The TAX PROPOSAL was rejected".
         // println!("noun_phrase.push 3");
@@ -190,7 +204,24 @@ pub fn parse_entities(
   // println!("noun_phrase_arrays: {:?}", noun_phrase_arrays.len());
   // dbg!(&noun_phrase_arrays);
 
+  // Names of people and companies are agents, not entities, e.g. "Apple" in "Apple hired Jane".
+  let name_positions = output_sentence
+    .agents
+    .iter()
+    .filter(|agent| matches!(agent, Agents::Person(_) | Agents::Company(_)))
+    .filter_map(|agent| parse_state.get_symbol_positions(&agent.get_symbol()))
+    .flatten()
+    .copied()
+    .collect::<Vec<usize>>();
+
   for noun_phrase in noun_phrase_arrays {
+    if noun_phrase
+      .iter()
+      .all(|word| name_positions.contains(&word.position))
+    {
+      continue;
+    }
+
     let entity_key = get_entity_key(&noun_phrase, part)?;
     // println!("entity_key: {}", &entity_key);
 
@@ -218,6 +249,10 @@ pub fn parse_entities(
               EntityProperties::Quantity { quantity: _ } => true,
               EntityProperties::Occurance { occurs: _ } => false,
               EntityProperties::Attribute { attribute: _ } => false,
+              EntityProperties::Negate { negate: _ } => false,
+              EntityProperties::Measurement { measurement: _ } => true,
+              EntityProperties::Members { members: _ } => true,
+              EntityProperties::Restriction { restriction: _ } => false,
             });
 
           // println!("has_plural_mod: {}", has_plural_mod);
@@ -239,6 +274,13 @@ pub fn parse_entities(
         .properties
         .extend(noun_mods);
 
+      // "coffee without milk"
+      if is_absent(&noun_phrase, part) && !is_negated(&entity.properties) {
+        entity
+          .properties
+          .push(EntityProperties::Negate { negate: true });
+      }
+
       let word_positions = noun_phrase
         .iter()
         .map(|word| word.position)
@@ -263,7 +305,11 @@ pub fn get_noun_modifiers(
 ) {
   // Stop at other A- links
   // TODO: There probably is better keys to trigger ending the recursion.
-  if word.has_disjunct(LinkTypes::A, ConnectorPointing::Left) {
+  // Noun modifiers ("cotton" in "large cotton shirt") can have A- links too, and are part of the
+  // chain.
+  if word.has_disjunct(LinkTypes::A, ConnectorPointing::Left)
+    && !word.has_disjunct(LinkTypes::AN, ConnectorPointing::Right)
+  {
     if !is_num_word(&word.get_cleaned_word()) {
       return;
     }
@@ -352,30 +398,36 @@ pub fn get_noun_modifiers(
     }
   }
 
-  // "DTi+" is used to link determiners with nouns
-  if word.has_pos(POS::Adjective)
-    && !word.has_raw_disjunct("DTi+")
-    && !word.has_raw_disjunct("ALx+")
-  /* Already added with "the" check above */
-  {
-    let mut amplifiers = vec![];
-
-    if let Some(prev_word) = part
+  // "no sugar", but not "no coffee with sugar"
+  if is_negative_determiner(word) {
+    let determines_noun = part
       .links
-      .get_prev_word(word)
-    {
-      if prev_word.has_disjunct(LinkTypes::EA, ConnectorPointing::Right) {
-        amplifiers.push(prev_word.get_cleaned_word());
-      }
+      .get_next_words(word)
+      .into_iter()
+      .find(|w| w.has_disjunct(LinkTypes::D, ConnectorPointing::Left))
+      .map(|w| {
+        w.position
+          >= noun
+            .first()
+            .unwrap()
+            .position
+      })
+      .unwrap_or(false);
+
+    if determines_noun {
+      entity_mods.push(EntityProperties::Quantity {
+        quantity: Quantities::None,
+      });
+      entity_mods.push(EntityProperties::Negate { negate: true });
     }
+  }
 
-    let mod_prop = EntityProperties::Modifier {
-      modifier_type: word.get_cleaned_word(),
-      modifier: None,
+  if let Some((modifier_type, modifier, amplifiers)) = adjective_modifier(word, part) {
+    entity_mods.push(EntityProperties::Modifier {
+      modifier_type,
+      modifier: Some(modifier),
       amplifiers,
-    };
-
-    entity_mods.push(mod_prop);
+    });
   }
 
   if word.position > 0 {
@@ -387,3 +439,92 @@ pub fn get_noun_modifiers(
     }
   }
 }
+
+// Whether the noun phrase is the object of "without".
+fn is_absent(noun_phrase: &[Word], part: &SentenceParts) -> bool {
+  noun_phrase
+    .iter()
+    .filter(|word| word.has_disjunct(LinkTypes::J, ConnectorPointing::Left))
+    .filter_map(|word| {
+      part
+        .links
+        .find_prev_word_with_link(word, LinkTypes::J, ConnectorPointing::Right)
+    })
+    .any(is_absence_preposition)
+}
+
+fn is_negated(properties: &[EntityProperties]) -> bool {
+  properties
+    .iter()
+    .any(|p| matches!(p, EntityProperties::Negate { negate: true }))
+}
+
+// An attributive adjective's category, the adjective, and its amplifiers, e.g.
+// ("color", "red", ["dark"]) for "dark red shirt". Adjectives are linked to their noun with A,
+// or with AJ to a conjunction that is ("black and white shirt"). Noun modifiers in the taxonomy
+// count too ("cotton shirt"). Amplifiers are adverbs linked with EA ("very") and the
+// category's own amplifiers ("dark").
+pub fn adjective_modifier(
+  word: &Word,
+  part: &SentenceParts,
+) -> Option<(String, String, Vec<String>)> {
+  let adjective = word
+    .get_cleaned_word()
+    .to_lowercase();
+
+  let is_attributive = word.has_pos(POS::Adjective)
+    && !word.has_raw_disjunct("DTi+") // "DTi+" is used to link determiners with nouns
+    && !word.has_raw_disjunct("ALx+") // Already added with "the" check
+    && (word.has_disjunct(LinkTypes::A, ConnectorPointing::Right)
+      || word.has_disjunct(LinkTypes::AJ, ConnectorPointing::Left)
+      || word.has_disjunct(LinkTypes::AJ, ConnectorPointing::Right));
+
+  let is_noun_modifier = word.has_disjunct(LinkTypes::AN, ConnectorPointing::Right)
+    && AdjectiveTaxonomy::contains(&adjective);
+
+  if !is_attributive && !is_noun_modifier {
+    return None;
+  }
+
+  // "dark" in "dark red" is an amplifier of "red".
+  let amplifies_next = part
+    .links
+    .get_next_word(word)
+    .map(|next| AdjectiveTaxonomy::amplifies(&adjective, &next.get_cleaned_word()))
+    .unwrap_or(false);
+
+  if amplifies_next {
+    return None;
+  }
+
+  let category_word = if AdjectiveTaxonomy::contains(&adjective) {
+    adjective.clone()
+  } else {
+    part
+      .get_word_lemma(word)
+      .to_lowercase()
+  };
+
+  let mut amplifiers = vec![];
+  let mut amplified = word;
+
+  while let Some(prev_word) = part
+    .links
+    .get_prev_word(amplified)
+  {
+    let prev = prev_word
+      .get_cleaned_word()
+      .to_lowercase();
+
+    if prev_word.has_disjunct(LinkTypes::EA, ConnectorPointing::Right)
+      || AdjectiveTaxonomy::amplifies(&prev, &category_word)
+    {
+      amplifiers.insert(0, prev);
+      amplified = prev_word;
+    } else {
+      break;
+    }
+  }
+
+  Some((AdjectiveTaxonomy::category_name(&category_word), adjective, amplifiers))
+}
@@ -9,10 +9,12 @@ use link_parser_rust_bindings::lp::{disjunct::ConnectorPointing, link_types::Lin
 use crate::{
   nlp::sentence_parts::SentenceParts,
   sema::{
+    action::ActionProperties,
     event::{Event, EventProperties},
     // entity::{Entity, EntityProperties},
     sema_sentence::SemaSentence,
     symbol::Symbol,
+    temporal::{Temporals, Tense, Tenses},
   },
 };
 
@@ -104,14 +106,94 @@ pub fn parse_events(
     }
   }
 
-  // Past tense verbs require an event.
-  // let _past_tense_verbs = part
-  //   .links
-  //   .get_past_tense_verbs();
+  // Past and future actions happen at a time other than now, so they need an event to hang
+  // that time on, e.g. "Jeff bought a computer", "I will bake a cake".
+  for action in sema_sentence
+    .actions
+    .iter()
+  {
+    let tense = action
+      .properties
+      .iter()
+      .find_map(|p| match p {
+        ActionProperties::Tense { tense } => Some(tense.clone()),
+        _ => None,
+      });
+
+    let tense = match tense {
+      Some(tense @ (Tenses::Past | Tenses::Future)) => Tense {
+        symbol: symbol.next_symbol(),
+        tense,
+      },
+      _ => continue,
+    };
+
+    output_sentence
+      .temporal
+      .push(Temporals::Tense(tense.clone()));
+
+    // "on" events already point at the action.
+    let existing_event = output_sentence
+      .events
+      .iter_mut()
+      .find(|event| {
+        event
+          .properties
+          .iter()
+          .any(|p| matches!(p, EventProperties::Action { action: a } if *a == action.symbol))
+      });
+
+    match existing_event {
+      Some(event) => event
+        .properties
+        .push(EventProperties::Tense { tense }),
+      None => {
+        let mut event = Event::new("event".to_string(), symbol);
+
+        event
+          .properties
+          .push(EventProperties::Action {
+            action: action.symbol.clone(),
+          });
+        event
+          .properties
+          .push(EventProperties::Tense { tense });
+
+        output_sentence
+          .events
+          .push(event);
+      }
+    }
+  }
+
+  // "I didn't buy a computer on Monday"
+  for event in output_sentence
+    .events
+    .iter_mut()
+  {
+    let negated = event
+      .properties
+      .iter()
+      .any(|p| match p {
+        EventProperties::Action { action } => sema_sentence
+          .actions
+          .iter()
+          .any(|a| {
+            a.symbol == *action
+              && a
+                .properties
+                .iter()
+                .any(|ap| matches!(ap, ActionProperties::Negate { negate: true }))
+          }),
+        _ => false,
+      });
+
+    if negated {
+      event
+        .properties
+        .push(EventProperties::Negate { negate: true });
+    }
+  }
 
-  /*
-  First off need to create an event for actions(verbs) that are in the past tense.
-  Also, maybe the future tense as well?
-   */
   Ok(output_sentence)
 }
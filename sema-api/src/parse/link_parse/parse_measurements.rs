@@ -0,0 +1,336 @@
+use anyhow::Result;
+use convert_case::{Case, Casing};
+
+use link_parser_rust_bindings::{
+  lp::{disjunct::ConnectorPointing, link_types::LinkTypes, word::Word},
+  pos::POS,
+};
+
+use crate::{
+  nlp::{duck::DuckValues, sentence_parts::SentenceParts},
+  parse::numbers::construct_number,
+  sema::{
+    entity::{Entity, EntityProperties},
+    measurement::{Dimensions, Measurement},
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+};
+
+use super::link_parse::ParseState;
+
+// Duckling dimensions that are measurements.
+pub static MEASUREMENT_DIMS: [&str; 5] = [
+  "quantity",
+  "distance",
+  "volume",
+  "temperature",
+  "amount-of-money",
+];
+
+// Attaches measurements to the entity they measure, e.g. "a 5 kg bag of rice", "$12.50 lunch",
+// "two cups of flour". Entities for the units themselves ("cups") are folded into the measured
+// entity. Measurements that don't measure an entity ("I ran 5 km") get an entity of their own.
+pub fn parse_measurements(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+) -> Result<SemaSentence> {
+  let mut output_sentence = sema_sentence.clone();
+  let mut measured_positions: Vec<usize> = vec![];
+
+  // measurement ducks
+  for p in part
+    .duck
+    .parts
+    .iter()
+    .filter(|p| MEASUREMENT_DIMS.contains(&p.dim.as_str()))
+  {
+    if let DuckValues::Measure {
+      value,
+      unit,
+      product,
+    } = &p.value
+    {
+      let positions = part.get_duck_word_positions(p);
+
+      // duckling can find the same words as more than one dimension
+      if positions.is_empty()
+        || positions
+          .iter()
+          .any(|p| measured_positions.contains(p))
+      {
+        continue;
+      }
+
+      let unit = unit
+        .clone()
+        .unwrap_or_default();
+
+      let measurement = if p.dim == "amount-of-money" {
+        Measurement::money(*value, &unit)
+      } else {
+        Measurement::new(*value, &unit)
+      };
+
+      measured_positions.extend(&positions);
+
+      attach_measurement(
+        &mut output_sentence,
+        part,
+        symbol,
+        parse_state,
+        measurement,
+        positions,
+        product.as_deref(),
+      );
+    }
+  }
+
+  // Units the link parser found that duckling didn't, e.g. "two cups".
+  for word in &part
+    .links
+    .words
+  {
+    if measured_positions.contains(&word.position) {
+      continue;
+    }
+
+    if let Some((measurement, positions)) = unit_measurement(word, part) {
+      measured_positions.extend(&positions);
+
+      attach_measurement(
+        &mut output_sentence,
+        part,
+        symbol,
+        parse_state,
+        measurement,
+        positions,
+        None,
+      );
+    }
+  }
+
+  Ok(output_sentence)
+}
+
+// A unit noun and the number in front of it, e.g. "two cups", "5 kg", or "a cup".
+fn unit_measurement(word: &Word, part: &SentenceParts) -> Option<(Measurement, Vec<usize>)> {
+  let is_noun = matches!(
+    word.pos,
+    Some(
+      POS::Noun
+        | POS::NounUncountable
+        | POS::PluralCountNoun
+        | POS::SingularMassNoun
+        | POS::UnitOfMeasurement
+    )
+  );
+
+  let unit = part
+    .get_word_lemma(word)
+    .to_lowercase();
+
+  if !is_noun || !Measurement::is_unit(&unit) {
+    return None;
+  }
+
+  let prev_word = part
+    .links
+    .get_prev_word(word)?;
+
+  let value = match construct_number(prev_word, part) {
+    Some(count) => count as f64,
+    None => {
+      let is_single = ["a", "an", "one"].contains(
+        &prev_word
+          .get_cleaned_word()
+          .to_lowercase()
+          .as_str(),
+      );
+
+      // The article of a compound isn't a count, e.g. "a pound cake".
+      if !is_single || word.has_disjunct(LinkTypes::AN, ConnectorPointing::Right) {
+        return None;
+      }
+
+      1.
+    }
+  };
+
+  Some((
+    Measurement::new(value, &unit),
+    vec![prev_word.position, word.position],
+  ))
+}
+
+// Whether a word is a unit with a number in front of it, rather than a noun that happens to
+// name one, e.g. "pound" in "2 pound bag" but not in "pound cake".
+pub fn is_unit_measurement(word: &Word, part: &SentenceParts) -> bool {
+  unit_measurement(word, part).is_some()
+}
+
+fn attach_measurement(
+  sentence: &mut SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+  measurement: Measurement,
+  positions: Vec<usize>,
+  product: Option<&str>,
+) {
+  let last = match positions
+    .iter()
+    .max()
+  {
+    Some(last) => *last,
+    None => return,
+  };
+
+  // duckling's product, e.g. "flour" in "two cups of flour"
+  let product_symbol = product.and_then(|product| {
+    let entity_type = product.to_case(Case::Snake);
+
+    sentence
+      .entities
+      .iter()
+      .find(|e| e.entity_type == entity_type)
+      .map(|e| e.get_symbol())
+  });
+
+  let measured_symbol = product_symbol
+    .or_else(|| {
+      of_object(part, &positions, last)
+        .and_then(|object| entity_at(sentence, parse_state, object.position))
+    })
+    .or_else(|| {
+      // "5 kg bag", "$12.50 lunch"
+      part
+        .links
+        .get_word_by_position(last + 1)
+        .filter(|next| {
+          next.has_disjunct(LinkTypes::AN, ConnectorPointing::Left)
+            || next.has_disjunct(LinkTypes::A, ConnectorPointing::Left)
+        })
+        .and_then(|next| entity_at(sentence, parse_state, next.position))
+    });
+
+  // Entities within the measurement are its unit, e.g. "cups" in "two cups of flour".
+  let unit_symbols = sentence
+    .entities
+    .iter()
+    .map(|e| e.get_symbol())
+    .filter(|s| Some(s) != measured_symbol.as_ref())
+    .filter(|s| {
+      parse_state
+        .get_symbol_positions(s)
+        .map(|ps| {
+          ps.iter()
+            .all(|p| positions.contains(p))
+        })
+        .unwrap_or(false)
+    })
+    .collect::<Vec<String>>();
+
+  sentence
+    .entities
+    .retain(|e| !unit_symbols.contains(&e.symbol));
+
+  let mut measured_positions = positions.clone();
+
+  for unit_symbol in &unit_symbols {
+    if let Some(unit_positions) = parse_state.remove_symbol(unit_symbol) {
+      measured_positions.extend(unit_positions);
+    }
+  }
+
+  let value = measurement.value;
+  let dimension = measurement.dimension;
+  let property = EntityProperties::Measurement { measurement };
+
+  match measured_symbol.and_then(|s| {
+    sentence
+      .entities
+      .iter_mut()
+      .find(|e| e.symbol == s)
+  }) {
+    Some(entity) => {
+      // the measurement's number isn't a count of the entity, e.g. "5" in "5 kg bag"
+      entity
+        .properties
+        .retain(|p| !matches!(p, EntityProperties::Count { count } if *count as f64 == value));
+
+      entity
+        .properties
+        .push(property);
+
+      if let Some(entity_positions) = parse_state.get_symbol_positions(&entity.symbol) {
+        measured_positions.extend(entity_positions);
+      }
+
+      measured_positions.sort();
+      measured_positions.dedup();
+
+      parse_state.add_symbol(&entity.symbol, measured_positions);
+    }
+    None => {
+      let mut entity = Entity::new(dimension_name(dimension), symbol);
+
+      entity
+        .properties
+        .push(property);
+
+      measured_positions.sort();
+      measured_positions.dedup();
+
+      parse_state.add_symbol(&entity.symbol, measured_positions);
+
+      sentence
+        .entities
+        .push(entity);
+    }
+  }
+}
+
+// The object of "of" in or right after a measurement, e.g. "flour" in "two cups of flour".
+fn of_object<'a>(part: &'a SentenceParts, positions: &[usize], last: usize) -> Option<&'a Word> {
+  let of = part
+    .links
+    .words
+    .iter()
+    .filter(|w| positions.contains(&w.position) || w.position == last + 1)
+    .find(|w| {
+      w.get_cleaned_word()
+        .eq_ignore_ascii_case("of")
+        && w.has_disjunct(LinkTypes::J, ConnectorPointing::Right)
+    })?;
+
+  part
+    .links
+    .get_next_words(of)
+    .into_iter()
+    .find(|w| w.has_disjunct(LinkTypes::J, ConnectorPointing::Left))
+}
+
+fn entity_at(sentence: &SemaSentence, parse_state: &ParseState, position: usize) -> Option<String> {
+  let symbols = parse_state.get_symbols_by_position(position);
+
+  sentence
+    .entities
+    .iter()
+    .find(|e| symbols.contains(&e.symbol))
+    .map(|e| e.get_symbol())
+}
+
+fn dimension_name(dimension: Dimensions) -> String {
+  match dimension {
+    Dimensions::Mass => "mass",
+    Dimensions::Volume => "volume",
+    Dimensions::Distance => "distance",
+    Dimensions::Temperature => "temperature",
+    Dimensions::Money => "money",
+    Dimensions::Quantity => "quantity",
+  }
+  .to_string()
+}
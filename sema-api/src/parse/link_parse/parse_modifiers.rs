@@ -0,0 +1,169 @@
+use link_parser_rust_bindings::{
+  lp::{disjunct::ConnectorPointing, link_types::LinkTypes, word::Word as LPWord},
+  pos::POS,
+};
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  sema::action::{ActionProperties, AdverbCategories},
+};
+
+use super::parse_negation::NEGATIVE_ADVERBS;
+
+pub static DEGREE_ADVERBS: [&str; 20] = [
+  "very",
+  "really",
+  "barely",
+  "hardly",
+  "scarcely",
+  "almost",
+  "nearly",
+  "completely",
+  "totally",
+  "fully",
+  "partly",
+  "partially",
+  "entirely",
+  "quite",
+  "rather",
+  "extremely",
+  "slightly",
+  "somewhat",
+  "too",
+  "only",
+];
+
+pub static FREQUENCY_ADVERBS: [&str; 16] = [
+  "once",
+  "twice",
+  "again",
+  "always",
+  "usually",
+  "often",
+  "sometimes",
+  "rarely",
+  "seldom",
+  "occasionally",
+  "frequently",
+  "regularly",
+  "normally",
+  "generally",
+  "daily",
+  "weekly",
+];
+
+pub static CERTAINTY_ADVERBS: [&str; 12] = [
+  "probably",
+  "possibly",
+  "maybe",
+  "perhaps",
+  "definitely",
+  "certainly",
+  "surely",
+  "likely",
+  "clearly",
+  "obviously",
+  "apparently",
+  "actually",
+];
+
+// Adverbs modifying a verb, with the adverbs amplifying them, e.g. "very carefully":
+// - E links adverbs in front of the verb: "Gently stir the sauce".
+// - MV links adverbs after the verb: "I called twice".
+// - EE links an adverb to the adverb it amplifies: "very carefully".
+pub fn verb_modifier_properties(part: &SentenceParts, v: &LPWord) -> Vec<ActionProperties> {
+  let before = part
+    .links
+    .words[..v.position]
+    .iter()
+    .filter(|w| w.has_disjunct(LinkTypes::E, ConnectorPointing::Right))
+    .filter(|w| {
+      part
+        .links
+        .find_next_word_with_link(w, LinkTypes::E, ConnectorPointing::Left)
+        .map(|next| next.position == v.position)
+        .unwrap_or(false)
+    });
+
+  let after = part
+    .links
+    .get_next_words(v)
+    .into_iter()
+    .filter(|w| w.has_disjunct(LinkTypes::MV, ConnectorPointing::Left))
+    .filter(|w| {
+      part
+        .links
+        .find_prev_word_with_link(w, LinkTypes::MV, ConnectorPointing::Right)
+        .map(|prev| prev.position == v.position)
+        .unwrap_or(false)
+    });
+
+  before
+    .chain(after)
+    .filter_map(|adverb| {
+      let modifier_type = adverb_category(adverb)?;
+
+      Some(ActionProperties::Modifier {
+        modifier_type,
+        modifier: Some(
+          adverb
+            .get_cleaned_word()
+            .to_lowercase(),
+        ),
+        amplifiers: adverb_amplifiers(part, adverb),
+      })
+    })
+    .collect()
+}
+
+// Listed adverbs, and otherwise any "-ly" adverb is taken to be manner. Adverbs the dictionary
+// has no subscript for are known by their links, e.g. "gently" (E) or "carefully" (MVa).
+// Negations are left to negation parsing.
+pub fn adverb_category(word: &LPWord) -> Option<AdverbCategories> {
+  let w = word
+    .get_cleaned_word()
+    .to_lowercase();
+
+  if NEGATIVE_ADVERBS.contains(&w.as_str()) {
+    None
+  } else if DEGREE_ADVERBS.contains(&w.as_str()) {
+    Some(AdverbCategories::Degree)
+  } else if FREQUENCY_ADVERBS.contains(&w.as_str()) {
+    Some(AdverbCategories::Frequency)
+  } else if CERTAINTY_ADVERBS.contains(&w.as_str()) {
+    Some(AdverbCategories::Certainty)
+  } else if is_adverb(word) && w.ends_with("ly") {
+    Some(AdverbCategories::Manner)
+  } else {
+    None
+  }
+}
+
+fn is_adverb(word: &LPWord) -> bool {
+  word.has_pos(POS::Adverb)
+    || word.has_disjunct(LinkTypes::E, ConnectorPointing::Right)
+    || word
+      .get_disjuncts(LinkTypes::MV)
+      .iter()
+      .any(|d| d.pointing == ConnectorPointing::Left && d.has_subscript(vec!["a"]))
+}
+
+fn adverb_amplifiers(part: &SentenceParts, adverb: &LPWord) -> Vec<String> {
+  part
+    .links
+    .words[..adverb.position]
+    .iter()
+    .filter(|w| w.has_disjunct(LinkTypes::EE, ConnectorPointing::Right))
+    .filter(|w| {
+      part
+        .links
+        .find_next_word_with_link(w, LinkTypes::EE, ConnectorPointing::Left)
+        .map(|next| next.position == adverb.position)
+        .unwrap_or(false)
+    })
+    .map(|w| {
+      w.get_cleaned_word()
+        .to_lowercase()
+    })
+    .collect()
+}
@@ -0,0 +1,124 @@
+use link_parser_rust_bindings::lp::{
+  disjunct::ConnectorPointing, link_types::LinkTypes, word::Word as LPWord,
+};
+
+use crate::nlp::sentence_parts::SentenceParts;
+
+use super::parse_tense::auxiliary_chain;
+
+pub static NEGATIVE_ADVERBS: [&str; 4] = ["not", "never", "cannot", "no_longer"];
+
+// "Nobody called", "I bought nothing". They negate the verb they're the subject or object of.
+pub static NEGATIVE_PRONOUNS: [&str; 6] = [
+  "nobody", "nothing", "noone", "no_one", "none", "nowhere",
+];
+
+// Whether a verb is negated anywhere in its auxiliary chain, e.g. "I can't go", "I should not have
+// called", "I have never called", "I no longer call", or by a negative subject or object.
+pub fn verb_is_negated(part: &SentenceParts, v: &LPWord) -> bool {
+  let head = auxiliary_chain(part, v).head;
+
+  // Adverbs in front of the chain, e.g. "never" in "I never call John".
+  let mut start = head.position;
+
+  while start > 0
+    && part.links.words[start - 1].has_disjunct(LinkTypes::E, ConnectorPointing::Right)
+  {
+    start -= 1;
+  }
+
+  let negated_chain = part.links.words[start..=v.position]
+    .iter()
+    .any(|w| is_negation_word(part, w));
+
+  if negated_chain {
+    return true;
+  }
+
+  let subject = if head.has_disjunct(LinkTypes::S, ConnectorPointing::Left) {
+    part
+      .links
+      .find_prev_word_with_link(&head, LinkTypes::S, ConnectorPointing::Right)
+  } else {
+    None
+  };
+
+  let object = if v.has_disjunct(LinkTypes::O, ConnectorPointing::Right) {
+    part
+      .links
+      .get_next_words_skip(v, 1)
+      .into_iter()
+      .find(|w| w.has_disjunct(LinkTypes::O, ConnectorPointing::Left))
+  } else {
+    None
+  };
+
+  subject
+    .into_iter()
+    .chain(object)
+    .any(is_negative_pronoun)
+}
+
+// "no sugar"
+pub fn is_negative_determiner(word: &LPWord) -> bool {
+  word
+    .get_cleaned_word()
+    .eq_ignore_ascii_case("no")
+    && word.has_disjunct(LinkTypes::D, ConnectorPointing::Right)
+}
+
+// "without milk"
+pub fn is_absence_preposition(word: &LPWord) -> bool {
+  word
+    .get_cleaned_word()
+    .eq_ignore_ascii_case("without")
+}
+
+fn is_negation_word(part: &SentenceParts, word: &LPWord) -> bool {
+  let w = word
+    .get_cleaned_word()
+    .to_lowercase();
+
+  if NEGATIVE_ADVERBS.contains(&w.as_str()) || w.ends_with("n't") {
+    return true;
+  }
+
+  // "no longer", when it isn't joined into one word
+  if w == "longer" {
+    let prev_is_no = part
+      .links
+      .get_prev_word(word)
+      .map(|prev| {
+        prev
+          .get_cleaned_word()
+          .eq_ignore_ascii_case("no")
+      })
+      .unwrap_or(false);
+
+    if prev_is_no {
+      return true;
+    }
+  }
+
+  // contractions the link parser keeps as one word, e.g. "don't" is "do" + "not"
+  part
+    .get_word_tokens(word)
+    .last()
+    .and_then(|token| {
+      token
+        .word
+        .tags
+        .first()
+    })
+    .map(|tag| tag.lemma.as_ref() == "not")
+    .unwrap_or(false)
+}
+
+fn is_negative_pronoun(word: &LPWord) -> bool {
+  NEGATIVE_PRONOUNS.contains(
+    &word
+      .get_cleaned_word()
+      .to_lowercase()
+      .as_str(),
+  )
+}
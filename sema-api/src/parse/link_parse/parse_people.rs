@@ -0,0 +1,360 @@
+use link_parser_rust_bindings::lp::{
+  disjunct::ConnectorPointing, link_types::LinkTypes, word::Word as LPWord,
+};
+
+use crate::{
+  nlp::{person_attributes::PersonAttributes, sentence_parts::SentenceParts},
+  sema::agents::{Genders, PersonProperties},
+};
+
+use super::link_parse::ParseState;
+
+// Family relations, and the gender they imply.
+pub static KINSHIP_TERMS: [(&str, Option<Genders>); 43] = [
+  ("mother", Some(Genders::Female)),
+  ("mom", Some(Genders::Female)),
+  ("mum", Some(Genders::Female)),
+  ("father", Some(Genders::Male)),
+  ("dad", Some(Genders::Male)),
+  ("parent", None),
+  ("son", Some(Genders::Male)),
+  ("daughter", Some(Genders::Female)),
+  ("child", None),
+  ("kid", None),
+  ("brother", Some(Genders::Male)),
+  ("sister", Some(Genders::Female)),
+  ("sibling", None),
+  ("husband", Some(Genders::Male)),
+  ("wife", Some(Genders::Female)),
+  ("spouse", None),
+  ("boyfriend", Some(Genders::Male)),
+  ("girlfriend", Some(Genders::Female)),
+  ("fiance", Some(Genders::Male)),
+  ("fiancee", Some(Genders::Female)),
+  ("grandmother", Some(Genders::Female)),
+  ("grandma", Some(Genders::Female)),
+  ("grandfather", Some(Genders::Male)),
+  ("grandpa", Some(Genders::Male)),
+  ("grandson", Some(Genders::Male)),
+  ("granddaughter", Some(Genders::Female)),
+  ("grandchild", None),
+  ("uncle", Some(Genders::Male)),
+  ("aunt", Some(Genders::Female)),
+  ("nephew", Some(Genders::Male)),
+  ("niece", Some(Genders::Female)),
+  ("cousin", None),
+  ("stepmother", Some(Genders::Female)),
+  ("stepfather", Some(Genders::Male)),
+  ("stepson", Some(Genders::Male)),
+  ("stepdaughter", Some(Genders::Female)),
+  ("mother-in-law", Some(Genders::Female)),
+  ("father-in-law", Some(Genders::Male)),
+  ("son-in-law", Some(Genders::Male)),
+  ("daughter-in-law", Some(Genders::Female)),
+  ("brother-in-law", Some(Genders::Male)),
+  ("sister-in-law", Some(Genders::Female)),
+  ("in-law", None),
+];
+
+// Determiners that say whose relative someone is, e.g. "my" in "my brother".
+pub static POSSESSIVE_DETERMINERS: [&str; 6] = ["my", "your", "his", "her", "our", "their"];
+
+// What's said about a person besides their name.
+#[derive(Debug, Default)]
+pub struct PersonDetails {
+  pub properties: Vec<PersonProperties>,
+  // The words the details come from, e.g. "actor" in "the actor Eddie Murphy".
+  pub positions: Vec<usize>,
+  pub genders: Vec<Genders>,
+  // How they're related, and the symbol of who they're related to.
+  pub kinship: Option<(String, String)>,
+}
+
+// The details given around a name:
+// - a noun that introduces it, e.g. "the actor Eddie Murphy" (GN) or "my sister Anna",
+// - an appositive before it, e.g. "my sister, Anna" (MX),
+// - an appositive after it, e.g. "John, a doctor from Boston" (MX).
+pub fn name_details(
+  part: &SentenceParts,
+  parse_state: &ParseState,
+  name_vec: &[LPWord],
+) -> PersonDetails {
+  let mut details = PersonDetails::default();
+
+  let (first, last) = match (name_vec.first(), name_vec.last()) {
+    (Some(first), Some(last)) => (first, last),
+    _ => return details,
+  };
+
+  let introducer = name_vec
+    .iter()
+    .find(|w| w.has_disjunct(LinkTypes::GN, ConnectorPointing::Left))
+    .and_then(|w| {
+      part
+        .links
+        .find_prev_word_with_link(w, LinkTypes::GN, ConnectorPointing::Right)
+    })
+    // A particle can introduce the rest of the name, e.g. "van" in "Ludwig van Beethoven"
+    .filter(|noun| noun.position < first.position)
+    .or_else(|| {
+      part
+        .links
+        .get_prev_word(first)
+        .filter(|w| is_person_noun(w))
+    })
+    .or_else(|| {
+      if first.has_disjunct(LinkTypes::MX, ConnectorPointing::Left) {
+        part
+          .links
+          .find_prev_word_with_link(first, LinkTypes::MX, ConnectorPointing::Right)
+      } else {
+        None
+      }
+    });
+
+  if let Some(noun) = introducer {
+    noun_details(part, parse_state, noun, false, &mut details);
+  }
+
+  let appositive = if last.has_disjunct(LinkTypes::MX, ConnectorPointing::Right) {
+    part
+      .links
+      .words[last.position + 1..]
+      .iter()
+      .find(|w| w.has_disjunct(LinkTypes::MX, ConnectorPointing::Left))
+  } else {
+    None
+  };
+
+  if let Some(noun) = appositive {
+    noun_details(part, parse_state, noun, true, &mut details);
+  }
+
+  details
+}
+
+// Relatives without a name, e.g. "my brother" or "Jane's husband". They're only people when
+// it's said whose relative they are.
+pub fn kin_nouns<'a>(part: &'a SentenceParts, parse_state: &ParseState) -> Vec<&'a LPWord> {
+  part
+    .links
+    .words
+    .iter()
+    .filter(|w| {
+      kinship_gender(&w.get_cleaned_word()).is_some()
+        && parse_state
+          .get_symbols_by_position(w.position)
+          .is_empty()
+        && noun_premodifiers(part, w)
+          .1
+          .map(is_possessive)
+          .unwrap_or(false)
+    })
+    .collect()
+}
+
+// The details a noun gives about the person it stands for, or describes.
+// An appositive is also kept whole as a description, e.g. "doctor_from_boston".
+pub fn noun_details(
+  part: &SentenceParts,
+  parse_state: &ParseState,
+  noun: &LPWord,
+  is_appositive: bool,
+  details: &mut PersonDetails,
+) {
+  let term = noun
+    .get_cleaned_word()
+    .to_lowercase();
+
+  let (premodifiers, determiner) = noun_premodifiers(part, noun);
+
+  details
+    .positions
+    .push(noun.position);
+
+  match kinship_gender(&term) {
+    Some(gender) => {
+      details
+        .properties
+        .push(PersonProperties::Kinship {
+          kinship: term.clone(),
+        });
+
+      details
+        .genders
+        .extend(gender);
+
+      if let Some(relative) = determiner.and_then(|d| relative_symbol(part, parse_state, d)) {
+        details.kinship = Some((term.clone(), relative));
+      }
+    }
+    None if PersonAttributes::is_occupation(&term) => {
+      details
+        .properties
+        .push(PersonProperties::Occupation {
+          occupation: term.clone(),
+        });
+    }
+    None if !is_appositive => {
+      details
+        .properties
+        .push(PersonProperties::Description {
+          description: term.clone(),
+        });
+    }
+    None => (),
+  }
+
+  for word in &premodifiers {
+    let modifier = word
+      .get_cleaned_word()
+      .to_lowercase();
+
+    if PersonAttributes::is_nationality(&modifier) {
+      details
+        .positions
+        .push(word.position);
+
+      details
+        .properties
+        .push(PersonProperties::Nationality {
+          nationality: modifier,
+        });
+    } else if let Some(age) = parse_age(&modifier) {
+      details
+        .positions
+        .push(word.position);
+
+      details
+        .properties
+        .push(PersonProperties::Age { age });
+    }
+  }
+
+  if is_appositive {
+    let start = premodifiers
+      .last()
+      .map(|w| w.position)
+      .unwrap_or(noun.position);
+
+    let description = part
+      .links
+      .words[start..]
+      .iter()
+      .take_while(|w| {
+        !w.is_right_wall
+          && ![",", ".", ";", "!", "?"].contains(&w.get_cleaned_word().as_str())
+      })
+      .map(|w| {
+        w.get_cleaned_word()
+          .to_lowercase()
+      })
+      .collect::<Vec<String>>()
+      .join("_");
+
+    details
+      .properties
+      .push(PersonProperties::Description { description });
+  }
+}
+
+// The adjectives in front of a noun, nearest first, and the determiner in front of them:
+// (["30-year-old"], "my") for "my 30-year-old sister".
+fn noun_premodifiers<'a>(
+  part: &'a SentenceParts,
+  noun: &LPWord,
+) -> (Vec<&'a LPWord>, Option<&'a LPWord>) {
+  let mut premodifiers = vec![];
+  let mut position = noun.position;
+
+  while let Some(prev) = position
+    .checked_sub(1)
+    .and_then(|p| {
+      part
+        .links
+        .get_word_by_position(p)
+    })
+  {
+    if prev.has_disjunct(LinkTypes::D, ConnectorPointing::Right) {
+      return (premodifiers, Some(prev));
+    }
+
+    if !prev.has_disjunct(LinkTypes::A, ConnectorPointing::Right)
+      && !prev.has_disjunct(LinkTypes::AN, ConnectorPointing::Right)
+    {
+      break;
+    }
+
+    premodifiers.push(prev);
+    position = prev.position;
+  }
+
+  (premodifiers, None)
+}
+
+// "my" or "'s"
+fn is_possessive(determiner: &LPWord) -> bool {
+  let d = determiner
+    .get_cleaned_word()
+    .to_lowercase();
+
+  POSSESSIVE_DETERMINERS.contains(&d.as_str()) || d == "'s" || d == "'"
+}
+
+// Whose relative someone is: "my" is the ego, and "Jane's" is Jane.
+fn relative_symbol(
+  part: &SentenceParts,
+  parse_state: &ParseState,
+  determiner: &LPWord,
+) -> Option<String> {
+  if !is_possessive(determiner) {
+    return None;
+  }
+
+  let owner = if determiner
+    .get_cleaned_word()
+    .starts_with('\'')
+  {
+    part
+      .links
+      .get_prev_word(determiner)?
+  } else {
+    determiner
+  };
+
+  parse_state
+    .get_symbols_by_position(owner.position)
+    .into_iter()
+    .next()
+}
+
+// Some(gender) for kinship terms, where the gender may be None, e.g. for "cousin".
+fn kinship_gender(word: &str) -> Option<Option<Genders>> {
+  let word = word.to_lowercase();
+
+  KINSHIP_TERMS
+    .iter()
+    .find(|(term, _)| *term == word)
+    .map(|(_, gender)| gender.clone())
+}
+
+pub fn is_person_noun(word: &LPWord) -> bool {
+  let word = word
+    .get_cleaned_word()
+    .to_lowercase();
+
+  kinship_gender(&word).is_some() || PersonAttributes::is_occupation(&word)
+}
+
+// "30-year-old" -> 30
+fn parse_age(word: &str) -> Option<u32> {
+  let (age, rest) = word.split_once('-')?;
+
+  if rest == "year-old" || rest == "years-old" {
+    age
+      .parse::<u32>()
+      .ok()
+  } else {
+    None
+  }
+}
@@ -0,0 +1,1089 @@
+use anyhow::Result;
+
+use link_parser_rust_bindings::lp::{disjunct::ConnectorPointing, link_types::LinkTypes};
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  parse::numbers::construct_number,
+  sema::{
+    action::{ActionProperties, AdverbCategories},
+    entity::EntityProperties,
+    event::{Event, EventProperties},
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+    temporal::{
+      Absolute, AbsoluteProperties, DaysOfWeek, DurationProperties, Interval, IntervalProperties,
+      Temporals,
+    },
+  },
+};
+
+use super::{
+  link_parse::ParseState, parse_temporal::get_month_index, parse_tense::HABITUAL_ADVERBS,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequencies {
+  Secondly,
+  Minutely,
+  Hourly,
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+impl Frequencies {
+  pub fn rrule_code(&self) -> &'static str {
+    match self {
+      Frequencies::Secondly => "SECONDLY",
+      Frequencies::Minutely => "MINUTELY",
+      Frequencies::Hourly => "HOURLY",
+      Frequencies::Daily => "DAILY",
+      Frequencies::Weekly => "WEEKLY",
+      Frequencies::Monthly => "MONTHLY",
+      Frequencies::Yearly => "YEARLY",
+    }
+  }
+
+  // The next smaller frequency, and how many of it make one of this, e.g. 24 hours in a day.
+  fn subdivision(&self) -> Option<(Frequencies, u32)> {
+    match self {
+      Frequencies::Minutely => Some((Frequencies::Secondly, 60)),
+      Frequencies::Hourly => Some((Frequencies::Minutely, 60)),
+      Frequencies::Daily => Some((Frequencies::Hourly, 24)),
+      Frequencies::Weekly => Some((Frequencies::Daily, 7)),
+      Frequencies::Yearly => Some((Frequencies::Monthly, 12)),
+      Frequencies::Secondly | Frequencies::Monthly => None,
+    }
+  }
+}
+
+// Adverbs that repeat an action at a fixed rate, and how many of the frequency they're apart,
+// e.g. "daily" in "remind me daily at 9am".
+pub static FREQUENCY_ADVERBS: [(&str, Frequencies, u32); 10] = [
+  ("hourly", Frequencies::Hourly, 1),
+  ("daily", Frequencies::Daily, 1),
+  ("nightly", Frequencies::Daily, 1),
+  ("weekly", Frequencies::Weekly, 1),
+  ("biweekly", Frequencies::Weekly, 2),
+  ("fortnightly", Frequencies::Weekly, 2),
+  ("monthly", Frequencies::Monthly, 1),
+  ("quarterly", Frequencies::Monthly, 3),
+  ("yearly", Frequencies::Yearly, 1),
+  ("annually", Frequencies::Yearly, 1),
+];
+
+// Words for how many times something happens, e.g. "twice" in "twice a week".
+pub static MULTIPLIERS: [(&str, u32); 3] = [("once", 1), ("twice", 2), ("thrice", 3)];
+
+// Words between a count and its period: "twice a week", "3 times per day".
+pub static PERIOD_WORDS: [&str; 5] = ["a", "an", "per", "each", "every"];
+
+// Parts of the day that make something daily: "every morning".
+pub static DAY_PARTS: [&str; 4] = ["morning", "afternoon", "evening", "night"];
+
+// A repeating time, as it's built up from the words.
+#[derive(Debug, Clone)]
+struct Recurrence {
+  frequency: Option<Frequencies>,
+  interval: u32, // "every other Friday" is every 2 weeks
+  times: u32,    // "twice a week"
+  days_of_week: Vec<DaysOfWeek>,
+  month: Option<u32>,
+  hour: Option<u32>,
+  minute: Option<u32>,
+  until: Option<Until>,
+  adverb: Option<String>,
+  positions: Vec<usize>,
+}
+
+// When a recurrence ends: the temporal's symbol, its ISO time, and the words after "until".
+#[derive(Debug, Clone)]
+struct Until {
+  symbol: String,
+  iso: String,
+  positions: Vec<usize>,
+}
+
+impl Recurrence {
+  fn new(frequency: Option<Frequencies>, interval: u32, positions: Vec<usize>) -> Self {
+    Self {
+      frequency,
+      interval,
+      times: 1,
+      days_of_week: vec![],
+      month: None,
+      hour: None,
+      minute: None,
+      until: None,
+      adverb: None,
+      positions,
+    }
+  }
+
+  // The iCalendar RRULE, when the recurrence has a fixed rate. Several times a period are spread
+  // evenly over it, e.g. "twice a day" is every 12 hours. Periods that can't be evenly split,
+  // like "twice a week", have no RRULE.
+  fn rrule(&self) -> Option<String> {
+    let mut frequency = self.frequency?;
+    let mut interval = self.interval;
+
+    if self.times > 1 {
+      let (smaller, count) = frequency.subdivision()?;
+      let total = count * interval;
+
+      if total % self.times != 0 {
+        return None;
+      }
+
+      frequency = smaller;
+      interval = total / self.times;
+    }
+
+    let mut parts = vec![format!("FREQ={}", frequency.rrule_code())];
+
+    if interval > 1 {
+      parts.push(format!("INTERVAL={}", interval));
+    }
+
+    if let Some(month) = self.month {
+      parts.push(format!("BYMONTH={}", month));
+    }
+
+    if !self
+      .days_of_week
+      .is_empty()
+    {
+      let days = self
+        .days_of_week
+        .iter()
+        .map(|d| d.rrule_code())
+        .collect::<Vec<&str>>()
+        .join(",");
+
+      parts.push(format!("BYDAY={}", days));
+    }
+
+    if let Some(hour) = self.hour {
+      parts.push(format!("BYHOUR={}", hour));
+      parts.push(format!(
+        "BYMINUTE={}",
+        self
+          .minute
+          .unwrap_or(0)
+      ));
+    }
+
+    if let Some(until) = &self.until {
+      // "2027-06-01T00:00:00.000-07:00" -> "20270601"
+      let date = until
+        .iso
+        .get(..10)
+        .unwrap_or_default()
+        .replace('-', "");
+
+      // UNTIL has to be a date-time when the occurrences have a time of day.
+      if self
+        .hour
+        .is_some()
+      {
+        parts.push(format!("UNTIL={}T235959", date));
+      } else {
+        parts.push(format!("UNTIL={}", date));
+      }
+    }
+
+    Some(parts.join(";"))
+  }
+
+  fn properties(&self) -> Vec<IntervalProperties> {
+    let mut properties = vec![];
+
+    if let Some(adverb) = &self.adverb {
+      properties.push(IntervalProperties::Frequency {
+        frequency: adverb.clone(),
+      });
+    }
+
+    let interval = self.interval as i32;
+
+    match self.frequency {
+      Some(Frequencies::Secondly) => {
+        properties.push(IntervalProperties::Seconds { seconds: interval })
+      }
+      Some(Frequencies::Minutely) => {
+        properties.push(IntervalProperties::Minutes { minutes: interval })
+      }
+      Some(Frequencies::Hourly) => properties.push(IntervalProperties::Hours { hours: interval }),
+      Some(Frequencies::Daily) => properties.push(IntervalProperties::Days { days: interval }),
+      Some(Frequencies::Weekly) => properties.push(IntervalProperties::Weeks { weeks: interval }),
+      Some(Frequencies::Monthly) => {
+        properties.push(IntervalProperties::Months { months: interval })
+      }
+      Some(Frequencies::Yearly) => properties.push(IntervalProperties::Years { years: interval }),
+      None => (),
+    }
+
+    if self.times > 1 {
+      properties.push(IntervalProperties::Times {
+        times: self.times as i32,
+      });
+    }
+
+    for day_of_week in &self.days_of_week {
+      properties.push(IntervalProperties::DayOfWeek {
+        day_of_week: day_of_week.clone(),
+      });
+    }
+
+    if let Some(month) = self.month {
+      properties.push(IntervalProperties::Month {
+        month: month as i32,
+      });
+    }
+
+    if let Some(hour) = self.hour {
+      properties.push(IntervalProperties::Hour { hour: hour as i32 });
+      properties.push(IntervalProperties::Minute {
+        minute: self
+          .minute
+          .unwrap_or(0) as i32,
+      });
+    }
+
+    if let Some(until) = &self.until {
+      properties.push(IntervalProperties::Until {
+        until: until
+          .symbol
+          .clone(),
+      });
+    }
+
+    if let Some(rrule) = self.rrule() {
+      properties.push(IntervalProperties::RRule { rrule });
+    }
+
+    properties
+  }
+}
+
+// Repeating times become interval temporals, and the event of the action they modify recurs at
+// them: "Remind me every Monday", "Take a pill twice a day", "Water the plants daily at 9am",
+// "every other Friday until June", "I usually walk to work".
+pub fn parse_recurrence(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+) -> Result<SemaSentence> {
+  let mut output_sentence = sema_sentence.clone();
+
+  let texts = part
+    .links
+    .words
+    .iter()
+    .map(|w| {
+      w.get_cleaned_word()
+        .to_lowercase()
+    })
+    .collect::<Vec<String>>();
+
+  let mut i = 0;
+
+  while i < texts.len() {
+    let mut recurrence = match recurrence_at(part, &texts, i) {
+      Some(recurrence) => recurrence,
+      None => {
+        i += 1;
+        continue;
+      }
+    };
+
+    add_modifiers(&output_sentence, parse_state, &texts, &mut recurrence);
+
+    recurrence
+      .positions
+      .sort();
+    recurrence
+      .positions
+      .dedup();
+
+    absorb_temporals(&mut output_sentence, part, symbol, parse_state, &mut recurrence);
+
+    i = recurrence
+      .positions
+      .last()
+      .map(|p| p + 1)
+      .unwrap_or(i + 1);
+
+    let interval = Temporals::Interval(Interval {
+      symbol: symbol.next_symbol(),
+      text: words_text(part, &recurrence.positions),
+      properties: recurrence.properties(),
+    });
+
+    let interval_symbol = interval.get_symbol();
+
+    parse_state.add_symbol(
+      &interval_symbol,
+      recurrence
+        .positions
+        .clone(),
+    );
+
+    output_sentence
+      .temporal
+      .push(interval);
+
+    if let Some(action_symbol) =
+      recurring_action(part, &output_sentence, parse_state, &recurrence.positions)
+    {
+      drop_frequency_modifiers(&mut output_sentence, &action_symbol, &texts, &recurrence.positions);
+
+      let existing_event = output_sentence
+        .events
+        .iter_mut()
+        .find(|event| {
+          event
+            .properties
+            .iter()
+            .any(|p| matches!(p, EventProperties::Action { action } if *action == action_symbol))
+        });
+
+      let recurs = EventProperties::Recurrence {
+        recurs: interval_symbol,
+      };
+
+      match existing_event {
+        Some(event) => event
+          .properties
+          .push(recurs),
+        None => {
+          let mut event = Event::new("event".to_string(), symbol);
+
+          event
+            .properties
+            .push(EventProperties::Action {
+              action: action_symbol,
+            });
+          event
+            .properties
+            .push(recurs);
+
+          output_sentence
+            .events
+            .push(event);
+        }
+      }
+    }
+  }
+
+  Ok(output_sentence)
+}
+
+// The recurrence says how often the action happens, so its adverb isn't also a frequency
+// modifier, e.g. "daily" in "Water the plants daily".
+fn drop_frequency_modifiers(
+  sentence: &mut SemaSentence,
+  action_symbol: &str,
+  texts: &[String],
+  positions: &[usize],
+) {
+  let words = positions
+    .iter()
+    .filter_map(|p| texts.get(*p))
+    .collect::<Vec<&String>>();
+
+  for action in sentence
+    .actions
+    .iter_mut()
+    .filter(|a| a.symbol == action_symbol)
+  {
+    action
+      .properties
+      .retain(|p| {
+        !matches!(
+          p,
+          ActionProperties::Modifier {
+            modifier_type: AdverbCategories::Frequency,
+            modifier: Some(modifier),
+            ..
+          } if words.contains(&modifier)
+        )
+      });
+  }
+}
+
+// Times duckling found in a recurrence's words are part of it, e.g. "Monday" in "every Monday",
+// which duckling reads as next Monday, and so are the events that occur at them, e.g. the ON
+// event of "on Mondays".
+fn absorb_temporals(
+  sentence: &mut SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+  recurrence: &mut Recurrence,
+) {
+  let absorbed = sentence
+    .temporal
+    .iter()
+    .filter(|t| match t {
+      // "June" in "until June" is kept as the end
+      Temporals::Absolute(absolute) => recurrence
+        .until
+        .as_ref()
+        .map(|until| until.symbol != absolute.symbol)
+        .unwrap_or(true),
+      Temporals::Duration(_) => true,
+      _ => false,
+    })
+    .map(|t| t.get_symbol())
+    .filter(|s| {
+      parse_state
+        .get_symbol_positions(s)
+        .map(|ps| {
+          ps.iter()
+            .any(|p| {
+              recurrence
+                .positions
+                .contains(p)
+            })
+        })
+        .unwrap_or(false)
+    })
+    .collect::<Vec<String>>();
+
+  if absorbed.is_empty() {
+    return;
+  }
+
+  sentence
+    .temporal
+    .retain(|t| !absorbed.contains(&t.get_symbol()));
+
+  for s in &absorbed {
+    parse_state.remove_symbol(s);
+  }
+
+  for event in sentence
+    .events
+    .iter_mut()
+  {
+    event
+      .properties
+      .retain(|p| !matches!(p, EventProperties::Occurance { occurs } if absorbed.contains(occurs)));
+  }
+
+  sentence
+    .events
+    .retain(|e| {
+      !e.properties
+        .is_empty()
+    });
+
+  for entity in sentence
+    .entities
+    .iter_mut()
+  {
+    entity
+      .properties
+      .retain(|p| !matches!(p, EntityProperties::Occurance { occurs } if absorbed.contains(occurs)));
+  }
+
+  // Duckling can read "Friday until June" as one duration, so the end gets a temporal of its own.
+  if let Some(until) = recurrence
+    .until
+    .as_mut()
+    .filter(|until| absorbed.contains(&until.symbol))
+  {
+    let absolute = Temporals::Absolute(Absolute {
+      symbol: symbol.next_symbol(),
+      text: words_text(part, &until.positions),
+      properties: vec![AbsoluteProperties::ISO {
+        iso: until
+          .iso
+          .clone(),
+      }],
+    });
+
+    until.symbol = absolute.get_symbol();
+    parse_state.add_symbol(
+      &until.symbol,
+      until
+        .positions
+        .clone(),
+    );
+
+    sentence
+      .temporal
+      .push(absolute);
+  }
+}
+
+// The words at some positions, e.g. "every other Friday".
+fn words_text(part: &SentenceParts, positions: &[usize]) -> String {
+  positions
+    .iter()
+    .filter_map(|p| {
+      part
+        .links
+        .get_word_by_position(*p)
+    })
+    .map(|w| w.get_cleaned_word())
+    .collect::<Vec<String>>()
+    .join(" ")
+}
+
+// A recurrence starting at a word:
+// - a frequency adverb, "daily", or one without a fixed rate, "usually",
+// - "every" or "each" and a period, "every other Friday", "every 2 weeks", "every Monday and
+//   Wednesday", "every June", "every morning",
+// - a plural day of the week, "on Mondays",
+// - a count and a period, "twice a week", "3 times per day", "once every 2 weeks".
+fn recurrence_at(part: &SentenceParts, texts: &[String], i: usize) -> Option<Recurrence> {
+  let word = part
+    .links
+    .get_word_by_position(i)?;
+  let text = texts[i].as_str();
+
+  if let Some((_, frequency, interval)) = FREQUENCY_ADVERBS
+    .iter()
+    .find(|(adverb, _, _)| *adverb == text)
+  {
+    // not "a weekly meeting"
+    if word.has_disjunct(LinkTypes::A, ConnectorPointing::Right) {
+      return None;
+    }
+
+    return Some(Recurrence::new(Some(*frequency), *interval, vec![i]));
+  }
+
+  if HABITUAL_ADVERBS.contains(&text) {
+    let mut recurrence = Recurrence::new(None, 1, vec![i]);
+    recurrence.adverb = Some(text.to_string());
+
+    return Some(recurrence);
+  }
+
+  if text == "every" || text == "each" {
+    return every_period(part, texts, i);
+  }
+
+  if let Some(day_of_week) = text
+    .strip_suffix('s')
+    .and_then(DaysOfWeek::from_str)
+  {
+    let mut recurrence = Recurrence::new(Some(Frequencies::Weekly), 1, vec![i]);
+    recurrence
+      .days_of_week
+      .push(day_of_week);
+
+    if i > 0 && texts[i - 1] == "on" {
+      recurrence
+        .positions
+        .push(i - 1);
+    }
+
+    add_coordinated_days(texts, &mut recurrence);
+
+    return Some(recurrence);
+  }
+
+  let (times, period_start) = match MULTIPLIERS
+    .iter()
+    .find(|(multiplier, _)| *multiplier == text)
+  {
+    Some((_, times)) => (*times, i + 1),
+    None
+      if texts
+        .get(i + 1)
+        .map(|t| t == "times")
+        .unwrap_or(false) =>
+    {
+      (number(part, texts, i)?, i + 2)
+    }
+    None => return None,
+  };
+
+  let period_word = texts.get(period_start)?;
+
+  if !PERIOD_WORDS.contains(&period_word.as_str()) {
+    return None;
+  }
+
+  let mut recurrence = if period_word == "every" || period_word == "each" {
+    every_period(part, texts, period_start)?
+  } else {
+    let (frequency, days_of_week) = time_unit(texts.get(period_start + 1)?)?;
+
+    let mut recurrence = Recurrence::new(Some(frequency), 1, vec![period_start, period_start + 1]);
+    recurrence.days_of_week = days_of_week;
+
+    recurrence
+  };
+
+  recurrence.times = times;
+  recurrence
+    .positions
+    .extend(i..period_start);
+
+  Some(recurrence)
+}
+
+// "every" and what comes after it.
+fn every_period(part: &SentenceParts, texts: &[String], every: usize) -> Option<Recurrence> {
+  let mut j = every + 1;
+  let mut interval = 1;
+
+  if texts
+    .get(j)
+    .map(|t| t == "other")
+    .unwrap_or(false)
+  {
+    interval = 2;
+    j += 1;
+  } else if let Some(n) = number(part, texts, j) {
+    interval = n;
+    j += 1;
+  }
+
+  let unit = texts.get(j)?;
+  let positions = (every..=j).collect::<Vec<usize>>();
+
+  if let Some((frequency, days_of_week)) = time_unit(unit) {
+    let mut recurrence = Recurrence::new(Some(frequency), interval, positions);
+    recurrence.days_of_week = days_of_week;
+
+    return Some(recurrence);
+  }
+
+  if let Some(day_of_week) = day_of_week(unit) {
+    let mut recurrence = Recurrence::new(Some(Frequencies::Weekly), interval, positions);
+    recurrence
+      .days_of_week
+      .push(day_of_week);
+
+    add_coordinated_days(texts, &mut recurrence);
+
+    return Some(recurrence);
+  }
+
+  let month = part
+    .links
+    .get_word_by_position(j)
+    .and_then(get_month_index)?;
+
+  let mut recurrence = Recurrence::new(Some(Frequencies::Yearly), interval, positions);
+  recurrence.month = Some(month as u32 + 1);
+
+  Some(recurrence)
+}
+
+// What follows a recurrence: "at 9am", "until June", "on Monday".
+fn add_modifiers(
+  sentence: &SemaSentence,
+  parse_state: &ParseState,
+  texts: &[String],
+  recurrence: &mut Recurrence,
+) {
+  loop {
+    let next = match recurrence
+      .positions
+      .iter()
+      .max()
+    {
+      Some(last) => last + 1,
+      None => return,
+    };
+
+    let modifier = match texts.get(next) {
+      Some(modifier) => modifier.as_str(),
+      None => return,
+    };
+
+    match modifier {
+      "at"
+        if recurrence
+          .hour
+          .is_none() =>
+      {
+        let (hour, minute, positions) = match clock_time(sentence, parse_state, texts, next + 1) {
+          Some(time) => time,
+          None => return,
+        };
+
+        recurrence.hour = Some(hour);
+        recurrence.minute = Some(minute);
+        recurrence
+          .positions
+          .push(next);
+        recurrence
+          .positions
+          .extend(positions);
+      }
+      "until" | "till"
+        if recurrence
+          .until
+          .is_none() =>
+      {
+        let (until, positions) = match until_date(sentence, parse_state, next + 1) {
+          Some(until) => until,
+          None => return,
+        };
+
+        recurrence.until = Some(until);
+        recurrence
+          .positions
+          .push(next);
+        recurrence
+          .positions
+          .extend(positions);
+      }
+      "on"
+        if recurrence.frequency == Some(Frequencies::Weekly)
+          && recurrence
+            .days_of_week
+            .is_empty() =>
+      {
+        let day_of_week = match texts
+          .get(next + 1)
+          .and_then(|t| day_of_week(t))
+        {
+          Some(day_of_week) => day_of_week,
+          None => return,
+        };
+
+        recurrence
+          .days_of_week
+          .push(day_of_week);
+        recurrence
+          .positions
+          .extend([next, next + 1]);
+
+        add_coordinated_days(texts, recurrence);
+      }
+      _ => return,
+    }
+  }
+}
+
+// "Monday and Wednesday", "Monday, Wednesday and Friday"
+fn add_coordinated_days(texts: &[String], recurrence: &mut Recurrence) {
+  while let Some(last) = recurrence
+    .positions
+    .iter()
+    .max()
+    .copied()
+  {
+    let is_conjunction = texts
+      .get(last + 1)
+      .map(|t| t == "and" || t == ",")
+      .unwrap_or(false);
+
+    let day = texts
+      .get(last + 2)
+      .and_then(|t| day_of_week(t));
+
+    match day {
+      Some(day) if is_conjunction => {
+        if !recurrence
+          .days_of_week
+          .contains(&day)
+        {
+          recurrence
+            .days_of_week
+            .push(day);
+        }
+
+        recurrence
+          .positions
+          .extend([last + 1, last + 2]);
+      }
+      _ => return,
+    }
+  }
+}
+
+// The hour and minute of a time of day, and the words it's made of. Duckling's time is used
+// when there is one, e.g. "9am", otherwise the words are read: "9 am", "9:30pm", "noon".
+fn clock_time(
+  sentence: &SemaSentence,
+  parse_state: &ParseState,
+  texts: &[String],
+  position: usize,
+) -> Option<(u32, u32, Vec<usize>)> {
+  for s in parse_state.get_symbols_by_position(position) {
+    let iso = sentence
+      .temporal
+      .iter()
+      .find_map(|t| match t {
+        Temporals::Absolute(absolute) if absolute.symbol == s => absolute
+          .properties
+          .iter()
+          .find_map(|p| match p {
+            AbsoluteProperties::ISO { iso } => Some(iso.clone()),
+            _ => None,
+          }),
+        _ => None,
+      });
+
+    // "2027-06-01T09:30:00.000-07:00"
+    let time = iso
+      .as_ref()
+      .and_then(|iso| iso.get(11..16))
+      .and_then(|time| time.split_once(':'))
+      .and_then(|(hour, minute)| {
+        Some((
+          hour
+            .parse::<u32>()
+            .ok()?,
+          minute
+            .parse::<u32>()
+            .ok()?,
+        ))
+      });
+
+    if let Some((hour, minute)) = time {
+      let positions = parse_state
+        .get_symbol_positions(&s)
+        .cloned()
+        .unwrap_or_default();
+
+      return Some((hour, minute, positions));
+    }
+  }
+
+  let text = texts.get(position)?;
+
+  match text.as_str() {
+    "noon" => return Some((12, 0, vec![position])),
+    "midnight" => return Some((0, 0, vec![position])),
+    _ => (),
+  }
+
+  let (clock, mut meridiem) = match text
+    .strip_suffix("am")
+    .map(|c| (c, Some("am")))
+    .or_else(|| {
+      text
+        .strip_suffix("pm")
+        .map(|c| (c, Some("pm")))
+    }) {
+    Some(split) => split,
+    None => (text.as_str(), None),
+  };
+
+  let mut positions = vec![position];
+
+  if meridiem.is_none() {
+    if let Some(next) = texts
+      .get(position + 1)
+      .filter(|t| *t == "am" || *t == "pm")
+    {
+      meridiem = Some(next.as_str());
+      positions.push(position + 1);
+    }
+  }
+
+  let (hour, minute) = match clock.split_once(':') {
+    Some((hour, minute)) => (hour, minute),
+    None => (clock, "0"),
+  };
+
+  let mut hour = hour
+    .parse::<u32>()
+    .ok()?;
+  let minute = minute
+    .parse::<u32>()
+    .ok()?;
+
+  if hour > 23 || minute > 59 || (meridiem.is_some() && !(1..=12).contains(&hour)) {
+    return None;
+  }
+
+  match meridiem {
+    Some("pm") if hour < 12 => hour += 12,
+    Some("am") if hour == 12 => hour = 0,
+    // "at 9" without am or pm isn't a time
+    None if !clock.contains(':') => return None,
+    _ => (),
+  }
+
+  Some((hour, minute, positions))
+}
+
+// The temporal a recurrence ends at, and all of its words.
+fn until_date(
+  sentence: &SemaSentence,
+  parse_state: &ParseState,
+  position: usize,
+) -> Option<(Until, Vec<usize>)> {
+  parse_state
+    .get_symbols_by_position(position)
+    .into_iter()
+    .find_map(|s| {
+      let iso = sentence
+        .temporal
+        .iter()
+        .find_map(|t| match t {
+          Temporals::Absolute(absolute) if absolute.symbol == s => absolute
+            .properties
+            .iter()
+            .find_map(|p| match p {
+              AbsoluteProperties::ISO { iso } => Some(iso.clone()),
+              _ => None,
+            }),
+          // duckling can give "Friday until June" as an interval ending in June
+          Temporals::Duration(duration) if duration.symbol == s => duration
+            .properties
+            .iter()
+            .find_map(|p| match p {
+              DurationProperties::End { end } => Some(end.clone()),
+              _ => None,
+            }),
+          _ => None,
+        })?;
+
+      // a date at least, "2027-06-01"
+      iso.get(..10)?;
+
+      let positions = parse_state
+        .get_symbol_positions(&s)
+        .cloned()
+        .unwrap_or_default();
+
+      let until = Until {
+        symbol: s,
+        iso,
+        positions: positions
+          .iter()
+          .copied()
+          .filter(|p| *p >= position)
+          .collect(),
+      };
+
+      Some((until, positions))
+    })
+}
+
+// The action a recurrence belongs to: the verb it modifies (MV, or E for adverbs in front of the
+// verb), or otherwise the nearest action.
+fn recurring_action(
+  part: &SentenceParts,
+  sentence: &SemaSentence,
+  parse_state: &ParseState,
+  positions: &[usize],
+) -> Option<String> {
+  let action_at = |position: usize| {
+    parse_state
+      .get_symbols_by_position(position)
+      .into_iter()
+      .find(|s| {
+        sentence
+          .actions
+          .iter()
+          .any(|a| &a.symbol == s)
+      })
+  };
+
+  for word in positions
+    .iter()
+    .filter_map(|p| {
+      part
+        .links
+        .get_word_by_position(*p)
+    })
+  {
+    let verb = if word.has_disjunct(LinkTypes::MV, ConnectorPointing::Left) {
+      part
+        .links
+        .find_prev_word_with_link(word, LinkTypes::MV, ConnectorPointing::Right)
+    } else if word.has_disjunct(LinkTypes::E, ConnectorPointing::Right) {
+      part
+        .links
+        .find_next_word_with_link(word, LinkTypes::E, ConnectorPointing::Left)
+    } else {
+      None
+    };
+
+    if let Some(action) = verb.and_then(|v| action_at(v.position)) {
+      return Some(action);
+    }
+  }
+
+  let start = *positions.first()?;
+
+  sentence
+    .actions
+    .iter()
+    .filter_map(|a| {
+      let position = *parse_state
+        .get_symbol_positions(&a.symbol)?
+        .first()?;
+
+      Some((a.symbol.clone(), position.abs_diff(start)))
+    })
+    .min_by_key(|(_, distance)| *distance)
+    .map(|(s, _)| s)
+}
+
+// "2" or "two"
+fn number(part: &SentenceParts, texts: &[String], position: usize) -> Option<u32> {
+  let word = part
+    .links
+    .get_word_by_position(position)?;
+
+  construct_number(word, part)
+    .or_else(|| {
+      texts[position]
+        .parse::<f32>()
+        .ok()
+    })
+    .filter(|n| *n >= 1. && n.fract() == 0.)
+    .map(|n| n as u32)
+}
+
+// "week", "days", "weekdays", "morning"
+fn time_unit(text: &str) -> Option<(Frequencies, Vec<DaysOfWeek>)> {
+  let unit = match text {
+    "second" | "seconds" => Frequencies::Secondly,
+    "minute" | "minutes" => Frequencies::Minutely,
+    "hour" | "hours" => Frequencies::Hourly,
+    "day" | "days" => Frequencies::Daily,
+    "week" | "weeks" => Frequencies::Weekly,
+    "month" | "months" => Frequencies::Monthly,
+    "year" | "years" => Frequencies::Yearly,
+    "weekday" | "weekdays" => {
+      return Some((
+        Frequencies::Weekly,
+        vec![
+          DaysOfWeek::Monday,
+          DaysOfWeek::Tuesday,
+          DaysOfWeek::Wednesday,
+          DaysOfWeek::Thursday,
+          DaysOfWeek::Friday,
+        ],
+      ))
+    }
+    "weekend" | "weekends" => {
+      return Some((
+        Frequencies::Weekly,
+        vec![DaysOfWeek::Saturday, DaysOfWeek::Sunday],
+      ))
+    }
+    _ if DAY_PARTS.contains(&text) => Frequencies::Daily,
+    _ => return None,
+  };
+
+  Some((unit, vec![]))
+}
+
+// "Monday" or "Mondays"
+fn day_of_week(text: &str) -> Option<DaysOfWeek> {
+  DaysOfWeek::from_str(text).or_else(|| {
+    text
+      .strip_suffix('s')
+      .and_then(DaysOfWeek::from_str)
+  })
+}
@@ -0,0 +1,369 @@
+use anyhow::Result;
+use link_parser_rust_bindings::lp::sentence::Sentence as LPSentence;
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  sema::{
+    action::{Action, ActionProperties},
+    diagnostic::{Diagnostic, DiagnosticKinds, DiagnosticStages},
+    location::Directions,
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+  services::allennlp_service::{SRLArgMModifiers, SRLArgs, SRLFrame},
+};
+
+use super::link_parse::ParseState;
+
+// Uses semantic role labels (if they were fetched for the sentence) to fill in action roles that the
+// link parser missed, and to flag the ones it disagrees with. The link parser's roles are always kept.
+pub fn cross_check_srl(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  _symbol: &mut Symbol,
+  parse_state: &mut ParseState,
+) -> Result<SemaSentence> {
+  let srl = match &part.srl {
+    Some(srl) => srl,
+    None => return Ok(sema_sentence.clone()),
+  };
+
+  let mut checked_sentence = sema_sentence.clone();
+
+  let alignment = align_tokens(&srl.words, &part.links);
+
+  for srl_frames in srl.frames()? {
+    let verb_positions = srl_frames
+      .frames
+      .iter()
+      .filter(|frame| frame.arg == SRLArgs::V)
+      .flat_map(|frame| frame_positions(frame, &alignment))
+      .collect::<Vec<usize>>();
+
+    let action = checked_sentence
+      .actions
+      .iter_mut()
+      .find(|action| {
+        parse_state
+          .get_symbol_positions(&action.symbol)
+          .map(|positions| {
+            positions
+              .iter()
+              .any(|p| verb_positions.contains(p))
+          })
+          .unwrap_or(false)
+      });
+
+    let action = match action {
+      Some(action) => action,
+      None => continue, // the link parser didn't make an action for this verb, verification handles that
+    };
+
+    for frame in srl_frames
+      .frames
+      .iter()
+    {
+      let mut symbols = vec![];
+
+      for position in frame_positions(frame, &alignment) {
+        for s in parse_state.get_symbols_by_position(position) {
+          if s != action.symbol && !symbols.contains(&s) {
+            symbols.push(s);
+          }
+        }
+      }
+
+      // Negations and directions are words, not symbols, e.g. "not" or "left" in "turn left".
+      if symbols.is_empty()
+        && !matches!(
+          frame.arg,
+          SRLArgs::ARGM(SRLArgMModifiers::NEG | SRLArgMModifiers::DIR)
+        )
+      {
+        continue;
+      }
+
+      check_frame(action, frame, symbols, &mut checked_sentence.diagnostics);
+    }
+  }
+
+  Ok(checked_sentence)
+}
+
+fn check_frame(action: &mut Action, frame: &SRLFrame, symbols: Vec<String>, diagnostics: &mut Vec<Diagnostic>) {
+  match &frame.arg {
+    SRLArgs::ARG0 => {
+      let agents = action_symbols(action, is_agent_property);
+      fill_or_compare(action, "ARG0", "agent", agents, symbols, diagnostics, |agent| {
+        ActionProperties::Agent { agent }
+      });
+    }
+    SRLArgs::ARG1 => {
+      let patients = action_symbols(action, is_patient_property);
+      fill_or_compare(action, "ARG1", "patient", patients, symbols, diagnostics, |patient| {
+        ActionProperties::Patient { patient }
+      });
+    }
+    SRLArgs::ARG2 | SRLArgs::ARG3 | SRLArgs::ARG4 => {
+      // Numbered args above 1 vary too much between rolesets to fill in, so only check they're covered.
+      let covered = action_symbols(action, |_| true);
+      let missing = symbols
+        .iter()
+        .filter(|s| !covered.contains(s))
+        .cloned()
+        .collect::<Vec<String>>();
+
+      if !missing.is_empty() {
+        diagnostics.push(Diagnostic::new(
+          DiagnosticStages::Srl,
+          DiagnosticKinds::Disagreement,
+          format!(
+            "srl labels {} as {:?} of {}, but the link parser didn't connect it",
+            missing.join(", "),
+            frame.arg,
+            action.symbol
+          ),
+          [vec![action.symbol.clone()], missing].concat(),
+        ));
+      }
+    }
+    SRLArgs::ARGM(SRLArgMModifiers::NEG) => {
+      let negated = action
+        .properties
+        .iter()
+        .any(|p| matches!(p, ActionProperties::Negate { negate: true }));
+
+      if !negated {
+        action
+          .properties
+          .push(ActionProperties::Negate { negate: true });
+
+        diagnostics.push(Diagnostic::new(
+          DiagnosticStages::Srl,
+          DiagnosticKinds::Repaired,
+          format!("added negation to {} from srl ARGM-NEG", action.symbol),
+          vec![action.symbol.clone()],
+        ));
+      }
+    }
+    SRLArgs::ARGM(SRLArgMModifiers::DIR) => {
+      let has_direction = action
+        .properties
+        .iter()
+        .any(|p| matches!(p, ActionProperties::Direction { .. }));
+
+      // Only spans with a direction word, e.g. "left", not "toward the door".
+      let direction = match frame
+        .words
+        .iter()
+        .find_map(|w| Directions::from_str(w))
+      {
+        Some(direction) if !has_direction => direction,
+        _ => return,
+      };
+
+      action
+        .properties
+        .push(ActionProperties::Direction { direction });
+
+      diagnostics.push(Diagnostic::new(
+        DiagnosticStages::Srl,
+        DiagnosticKinds::Repaired,
+        format!("added direction \"{}\" to {} from srl ARGM-DIR", frame.text(), action.symbol),
+        vec![action.symbol.clone()],
+      ));
+    }
+    SRLArgs::ARGM(modifier) => {
+      let to_property: fn(String) -> ActionProperties = match modifier {
+        SRLArgMModifiers::LOC => |location| ActionProperties::Location { location },
+        SRLArgMModifiers::GOL => |destination| ActionProperties::Destination { destination },
+        SRLArgMModifiers::COM => |co_agent| ActionProperties::CoAgent { co_agent },
+        SRLArgMModifiers::PRP | SRLArgMModifiers::CAU => |purpose| ActionProperties::Purpose { purpose },
+        _ => return,
+      };
+
+      let covered = action_symbols(action, |_| true);
+
+      for s in symbols {
+        if covered.contains(&s) {
+          continue;
+        }
+
+        action
+          .properties
+          .push(to_property(s.clone()));
+
+        diagnostics.push(Diagnostic::new(
+          DiagnosticStages::Srl,
+          DiagnosticKinds::Repaired,
+          format!("added {} to {} from srl ARGM-{:?}", s, action.symbol, modifier),
+          vec![action.symbol.clone(), s],
+        ));
+      }
+    }
+    _ => (),
+  }
+}
+
+fn fill_or_compare(
+  action: &mut Action,
+  srl_arg: &str,
+  role: &str,
+  existing: Vec<String>,
+  symbols: Vec<String>,
+  diagnostics: &mut Vec<Diagnostic>,
+  to_property: impl Fn(String) -> ActionProperties,
+) {
+  if existing.is_empty() {
+    for s in symbols {
+      action
+        .properties
+        .push(to_property(s.clone()));
+
+      diagnostics.push(Diagnostic::new(
+        DiagnosticStages::Srl,
+        DiagnosticKinds::Repaired,
+        format!("added {} {} to {} from srl {}", role, s, action.symbol, srl_arg),
+        vec![action.symbol.clone(), s],
+      ));
+    }
+  } else if !symbols
+    .iter()
+    .any(|s| existing.contains(s))
+  {
+    diagnostics.push(Diagnostic::new(
+      DiagnosticStages::Srl,
+      DiagnosticKinds::Disagreement,
+      format!(
+        "link parser {} of {} is {}, but srl {} is {}",
+        role,
+        action.symbol,
+        existing.join(", "),
+        srl_arg,
+        symbols.join(", ")
+      ),
+      [vec![action.symbol.clone()], existing, symbols].concat(),
+    ));
+  }
+}
+
+fn is_agent_property(property: &ActionProperties) -> bool {
+  matches!(
+    property,
+    ActionProperties::Agent { .. } | ActionProperties::Experiencer { .. }
+  )
+}
+
+fn is_patient_property(property: &ActionProperties) -> bool {
+  matches!(
+    property,
+    ActionProperties::Patient { .. }
+      | ActionProperties::Theme { .. }
+      | ActionProperties::Stimulus { .. }
+      | ActionProperties::Topic { .. }
+      | ActionProperties::Outcome { .. }
+  )
+}
+
+// The symbols an action's properties point at, for the properties matching the filter.
+fn action_symbols(action: &Action, filter: impl Fn(&ActionProperties) -> bool) -> Vec<String> {
+  action
+    .properties
+    .iter()
+    .filter(|p| filter(p))
+    .filter_map(|p| match p {
+      ActionProperties::Agent { agent: s }
+      | ActionProperties::Patient { patient: s }
+      | ActionProperties::Instrument { instrument: s }
+      | ActionProperties::Benefactive { benefactive: s }
+      | ActionProperties::Outcome { outcome: s }
+      | ActionProperties::Recipient { recipient: s }
+      | ActionProperties::Attribute { attribute: s }
+      | ActionProperties::Purpose { purpose: s }
+      | ActionProperties::Proposition { proposition: s }
+      | ActionProperties::Theme { theme: s }
+      | ActionProperties::CoAgent { co_agent: s }
+      | ActionProperties::Experiencer { experiencer: s }
+      | ActionProperties::Stimulus { stimulus: s }
+      | ActionProperties::Source { source: s }
+      | ActionProperties::Destination { destination: s }
+      | ActionProperties::Location { location: s }
+      | ActionProperties::Topic { topic: s } => Some(s.clone()),
+      ActionProperties::Direction { .. }
+      | ActionProperties::Negate { .. }
+      | ActionProperties::Tense { .. }
+      | ActionProperties::Aspect { .. }
+      | ActionProperties::Modifier { .. }
+      | ActionProperties::Modality { .. }
+      | ActionProperties::Voice { .. } => None,
+    })
+    .collect()
+}
+
+fn frame_positions(frame: &SRLFrame, alignment: &[Option<usize>]) -> Vec<usize> {
+  frame
+    .token_indices
+    .iter()
+    .filter_map(|i| {
+      alignment
+        .get(*i)
+        .copied()
+        .flatten()
+    })
+    .collect()
+}
+
+fn normalize(word: &str) -> String {
+  word
+    .to_lowercase()
+    .chars()
+    .filter(|c| c.is_alphanumeric())
+    .collect()
+}
+
+// Maps each SRL token to a link parser word position.
+// The two tokenizers mostly agree, but SRL splits contractions ("do", "n't") where the link parser
+// may not, so a token can also match a word that contains it.
+fn align_tokens(srl_words: &[String], links: &LPSentence) -> Vec<Option<usize>> {
+  let lp_words = links
+    .words
+    .iter()
+    .filter(|w| !w.is_left_wall && !w.is_right_wall)
+    .map(|w| (w.position, normalize(&w.get_cleaned_word())))
+    .collect::<Vec<(usize, String)>>();
+
+  let mut alignment = vec![];
+  let mut cursor = 0;
+
+  for srl_word in srl_words {
+    let srl_word = normalize(srl_word);
+
+    if srl_word.is_empty() {
+      alignment.push(None);
+      continue;
+    }
+
+    // Only look a few words ahead, so one bad match doesn't throw off the rest of the sentence.
+    let found = lp_words
+      .iter()
+      .enumerate()
+      .skip(cursor)
+      .take(3)
+      .find(|(_, (_, lp_word))| *lp_word == srl_word || (!lp_word.is_empty() && lp_word.contains(&srl_word)));
+
+    match found {
+      Some((i, (position, lp_word))) => {
+        alignment.push(Some(*position));
+        // Stay on a word that was only partly matched, the next token may be the rest of it.
+        cursor = if *lp_word == srl_word || lp_word.ends_with(&srl_word) {
+          i + 1
+        } else {
+          i
+        };
+      }
+      None => alignment.push(None),
+    }
+  }
+
+  alignment
+}
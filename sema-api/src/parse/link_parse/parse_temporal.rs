@@ -253,6 +253,7 @@ pub fn parse_temporal(
           .temporal
           .push(temporal);
       }
+      DuckValues::Measure { .. } => (),
     });
 
   // dbg!(&time_ducks);
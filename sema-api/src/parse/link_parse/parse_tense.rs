@@ -0,0 +1,376 @@
+use link_parser_rust_bindings::{
+  lp::{disjunct::ConnectorPointing, link_types::LinkTypes, word::Word as LPWord},
+  pos::POS,
+};
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  sema::{
+    action::{ActionProperties, Aspects, Modalities, Voices},
+    temporal::Tenses,
+  },
+};
+
+// Adverbs that make a simple tense habitual, e.g. "I usually call John".
+pub static HABITUAL_ADVERBS: [&str; 8] = [
+  "always",
+  "usually",
+  "often",
+  "normally",
+  "generally",
+  "regularly",
+  "frequently",
+  "sometimes",
+];
+
+// Forms of "be" and "get" in front of a passive past participle, e.g. "was chased", "got fired".
+pub static PASSIVE_AUXILIARIES: [&str; 13] = [
+  "be", "am", "is", "are", "was", "were", "been", "being", "get", "gets", "got", "gotten",
+  "getting",
+];
+
+// Forms of "be" in front of a present participle, e.g. "am" in "I am calling John".
+pub static BE_FORMS: [&str; 8] = ["be", "am", "is", "are", "was", "were", "been", "being"];
+
+// Forms of "do" in front of an infinitive, e.g. "don't" in "I don't know".
+pub static DO_AUXILIARIES: [&str; 6] = ["do", "does", "did", "don't", "doesn't", "didn't"];
+
+// A verb and the auxiliaries in front of it, e.g. "should have called".
+pub struct AuxiliaryChain {
+  pub head: LPWord, // the first word of the chain, which carries the tense
+  pub aspects: Vec<Aspects>,
+  pub modality: Option<Modalities>,
+  pub is_infinitive: bool,
+  pub is_passive: bool,
+}
+
+// Walks back from a verb through its auxiliaries:
+// - I links connect modals, "do" and "to" to the verb, e.g. "should" -> "have", "will" -> "bake".
+// - PP links connect "have" to a past participle, e.g. "have" -> "called" (perfect).
+// - Pg links connect "be" to a present participle, e.g. "am" -> "calling" (progressive), see
+//   progressive_auxiliary.
+// - Pv links connect "be" or "get" to a past participle, e.g. "was" -> "chased" (passive).
+pub fn auxiliary_chain(part: &SentenceParts, v: &LPWord) -> AuxiliaryChain {
+  let mut aspects = vec![];
+  let mut modality = None;
+  let mut is_infinitive = false;
+  let mut is_passive = false;
+
+  let mut head = v.clone();
+
+  loop {
+    if !is_passive {
+      if let Some(be) = passive_auxiliary(part, &head) {
+        is_passive = true;
+        head = be.clone();
+        continue;
+      }
+    }
+
+    if head.has_disjunct(LinkTypes::PP, ConnectorPointing::Left) {
+      if let Some(have) = part
+        .links
+        .find_prev_word_with_link(&head, LinkTypes::PP, ConnectorPointing::Right)
+      {
+        aspects.push(Aspects::Perfect);
+        head = have.clone();
+        continue;
+      }
+    }
+
+    if let Some(be) = progressive_auxiliary(part, &head) {
+      aspects.push(Aspects::Progressive);
+      head = be.clone();
+      continue;
+    }
+
+    if head.has_disjunct(LinkTypes::I, ConnectorPointing::Left) {
+      if let Some(auxiliary) = part
+        .links
+        .find_prev_word_with_link(&head, LinkTypes::I, ConnectorPointing::Right)
+      {
+        let auxiliary_word = auxiliary
+          .get_cleaned_word()
+          .to_lowercase();
+
+        if auxiliary_word == "to" {
+          // "I used to call John"
+          match used_to(part, auxiliary) {
+            Some(used) => {
+              aspects.push(Aspects::Habitual);
+              head = used.clone();
+              continue;
+            }
+            None => {
+              is_infinitive = true;
+              break;
+            }
+          }
+        }
+
+        // "help" in "help me move" is an action of its own, see parse_clauses.
+        let is_verb = matches!(
+          auxiliary.pos,
+          Some(POS::Verb | POS::Verb2 | POS::VerbPastTense | POS::VerbPastTense2)
+        );
+
+        if is_verb && !is_infinitive_auxiliary(auxiliary) {
+          is_infinitive = true;
+          break;
+        }
+
+        if let Some(m) = Modalities::from_str(&auxiliary_word) {
+          modality = Some(m);
+        }
+
+        // modals and "do", e.g. "I don't know"
+        head = auxiliary.clone();
+        continue;
+      }
+    }
+
+    break;
+  }
+
+  AuxiliaryChain {
+    head,
+    aspects,
+    modality,
+    is_infinitive,
+    is_passive,
+  }
+}
+
+// The tense, aspect, modality and voice of a verb. The first word of its auxiliary chain decides
+// the tense. Imperatives and infinitives have no tense.
+pub fn verb_tense_properties(part: &SentenceParts, v: &LPWord) -> Vec<ActionProperties> {
+  let AuxiliaryChain {
+    head,
+    mut aspects,
+    modality,
+    is_infinitive,
+    is_passive,
+  } = auxiliary_chain(part, v);
+
+  // "I usually call John", "I call John often"
+  let adverb_before = v.has_disjunct(LinkTypes::E, ConnectorPointing::Left)
+    && part
+      .links
+      .find_prev_word_with_link(v, LinkTypes::E, ConnectorPointing::Right)
+      .map(is_habitual_adverb)
+      .unwrap_or(false);
+
+  let adverb_after = v.has_disjunct(LinkTypes::MV, ConnectorPointing::Right)
+    && part
+      .links
+      .get_next_words_skip(v, 1)
+      .into_iter()
+      .find(|w| w.has_disjunct(LinkTypes::MV, ConnectorPointing::Left))
+      .map(is_habitual_adverb)
+      .unwrap_or(false);
+
+  let has_habitual_adverb = adverb_before || adverb_after;
+
+  if has_habitual_adverb && !aspects.contains(&Aspects::Habitual) {
+    aspects.push(Aspects::Habitual);
+  }
+
+  let mut properties = vec![];
+
+  let tense = if is_infinitive || is_imperative_verb(&head) {
+    None
+  } else if matches!(modality, Some(Modalities::Will | Modalities::Shall)) {
+    Some(Tenses::Future)
+  } else if matches!(head.pos, Some(POS::VerbPastTense | POS::VerbPastTense2)) {
+    Some(Tenses::Past)
+  } else {
+    Some(Tenses::Present)
+  };
+
+  if let Some(tense) = tense {
+    properties.push(ActionProperties::Tense { tense });
+  }
+
+  // Aspects were found from the verb outwards, so flip them to sentence order.
+  for aspect in aspects
+    .into_iter()
+    .rev()
+  {
+    properties.push(ActionProperties::Aspect { aspect });
+  }
+
+  if let Some(modality) = modality {
+    properties.push(ActionProperties::Modality { modality });
+  }
+
+  if is_passive {
+    properties.push(ActionProperties::Voice {
+      voice: Voices::Passive,
+    });
+  }
+
+  properties
+}
+
+// "have" in "I have called John", "am" in "I am calling John", "was" in "John was called", or
+// "will" in "I will call John". Their verb is the action.
+pub fn is_auxiliary_verb(part: &SentenceParts, v: &LPWord) -> bool {
+  v.has_disjunct(LinkTypes::PP, ConnectorPointing::Right)
+    || (v.has_disjunct(LinkTypes::I, ConnectorPointing::Right) && is_infinitive_auxiliary(v))
+    || has_progressive_link(v, ConnectorPointing::Right)
+    || part
+      .links
+      .get_next_words(v)
+      .into_iter()
+      .filter(|w| matches!(w.pos, Some(POS::Gerund)))
+      .filter_map(|w| progressive_auxiliary(part, w))
+      .any(|be| be.position == v.position)
+    || has_passive_link(v, ConnectorPointing::Right)
+    || part
+      .links
+      .get_next_word(v)
+      .and_then(|next| passive_auxiliary(part, next))
+      .map(|be| be.position == v.position)
+      .unwrap_or(false)
+    || part
+      .links
+      .find_next_word_with_link(v, LinkTypes::TO, ConnectorPointing::Left)
+      .map(|to| used_to(part, to).map(|used| used.position) == Some(v.position))
+      .unwrap_or(false)
+}
+
+// Modals and "do", which take an infinitive without being actions themselves.
+pub fn is_infinitive_auxiliary(word: &LPWord) -> bool {
+  let word = word
+    .get_cleaned_word()
+    .to_lowercase();
+
+  Modalities::from_str(&word).is_some() || DO_AUXILIARIES.contains(&word.as_str())
+}
+
+// The "be" or "get" that makes a verb passive, e.g. "was" in "the cat was chased by the dog".
+// Past participles right after one are passive too, when the link parser didn't use Pv, e.g.
+// "eaten" in "the cake was eaten" is only a participle, so it's linked as an adjective (Pa).
+pub fn passive_auxiliary<'a>(part: &'a SentenceParts, v: &LPWord) -> Option<&'a LPWord> {
+  if has_passive_link(v, ConnectorPointing::Left) {
+    return part
+      .links
+      .words[..v.position]
+      .iter()
+      .rev()
+      .find(|w| has_passive_link(w, ConnectorPointing::Right));
+  }
+
+  let is_participle = match v.pos {
+    Some(POS::VerbPastTense | POS::VerbPastTense2) => true,
+    Some(POS::Verb) => has_predicative_link(v, ConnectorPointing::Left),
+    _ => false,
+  };
+
+  if !is_participle {
+    return None;
+  }
+
+  v.position
+    .checked_sub(1)
+    .and_then(|p| {
+      part
+        .links
+        .get_word_by_position(p)
+    })
+    .filter(|prev| {
+      PASSIVE_AUXILIARIES.contains(
+        &prev
+          .get_cleaned_word()
+          .to_lowercase()
+          .as_str(),
+      )
+    })
+}
+
+// The "be" that makes a verb progressive, e.g. "am" in "I am calling John". A present participle
+// after "be" is sometimes linked as its object (O) instead, e.g. "working" in "I will be working"
+// is a gerund, so those are progressive too.
+pub fn progressive_auxiliary<'a>(part: &'a SentenceParts, v: &LPWord) -> Option<&'a LPWord> {
+  if has_progressive_link(v, ConnectorPointing::Left) {
+    return part
+      .links
+      .words[..v.position]
+      .iter()
+      .rev()
+      .find(|w| has_progressive_link(w, ConnectorPointing::Right));
+  }
+
+  if !matches!(v.pos, Some(POS::Gerund)) || !v.has_disjunct(LinkTypes::O, ConnectorPointing::Left) {
+    return None;
+  }
+
+  part
+    .links
+    .find_prev_word_with_link(v, LinkTypes::O, ConnectorPointing::Right)
+    .filter(|be| {
+      BE_FORMS.contains(
+        &be
+          .get_cleaned_word()
+          .to_lowercase()
+          .as_str(),
+      )
+    })
+}
+
+// Pv: http://www.abisource.com/projects/link-grammar/dict/section-P.html
+fn has_passive_link(word: &LPWord, conn_pointing: ConnectorPointing) -> bool {
+  word
+    .get_disjuncts(LinkTypes::P)
+    .iter()
+    .any(|d| d.pointing == conn_pointing && d.has_subscript(vec!["v"]))
+}
+
+// Pa: http://www.abisource.com/projects/link-grammar/dict/section-P.html
+fn has_predicative_link(word: &LPWord, conn_pointing: ConnectorPointing) -> bool {
+  word
+    .get_disjuncts(LinkTypes::P)
+    .iter()
+    .any(|d| d.pointing == conn_pointing && d.has_subscript(vec!["a"]))
+}
+
+// Pg: http://www.abisource.com/projects/link-grammar/dict/section-P.html
+fn has_progressive_link(word: &LPWord, conn_pointing: ConnectorPointing) -> bool {
+  word
+    .get_disjuncts(LinkTypes::P)
+    .iter()
+    .any(|d| d.pointing == conn_pointing && d.has_subscript(vec!["g"]))
+}
+
+// Wi connects the left wall to the verb of an imperative.
+pub fn is_imperative_verb(word: &LPWord) -> bool {
+  word
+    .get_disjuncts(LinkTypes::W)
+    .iter()
+    .any(|d| d.pointing == ConnectorPointing::Left && d.has_subscript(vec!["i"]))
+}
+
+// The "used" of "used to", given the "to".
+fn used_to<'a>(part: &'a SentenceParts, to: &LPWord) -> Option<&'a LPWord> {
+  if !to.has_disjunct(LinkTypes::TO, ConnectorPointing::Left) {
+    return None;
+  }
+
+  part
+    .links
+    .find_prev_word_with_link(to, LinkTypes::TO, ConnectorPointing::Right)
+    .filter(|w| {
+      w.get_cleaned_word()
+        .eq_ignore_ascii_case("used")
+        && matches!(w.pos, Some(POS::VerbPastTense | POS::VerbPastTense2))
+    })
+}
+
+fn is_habitual_adverb(word: &LPWord) -> bool {
+  HABITUAL_ADVERBS.contains(
+    &word
+      .get_cleaned_word()
+      .to_lowercase()
+      .as_str(),
+  )
+}
@@ -1,4 +1,5 @@
 pub mod token_parse;
 pub mod link_parse;
 
+pub mod mood;
 pub mod numbers;
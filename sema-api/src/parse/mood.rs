@@ -0,0 +1,120 @@
+use anyhow::Result;
+use link_parser_rust_bindings::lp::{disjunct::ConnectorPointing, link_types::LinkTypes};
+
+use crate::{
+  nlp::{sentence_parts::SentenceParts, treebank_pos::TreebankPOS},
+  parse::{
+    link_parse::{
+      link_parse::slot_property,
+      parse_args::assign_propbank_args,
+    },
+    token_parse::token_ir_state::{get_token_lemma, token_has_pos},
+  },
+  sema::{
+    action::ActionProperties,
+    agents::{Addressee, Agents},
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+  sentence::SentenceTypes,
+  verbnet::verbnet_classes::{ArgumentSlots, VerbNetClasses},
+  wordnet::wordnet_verbs::WordnetVerbs,
+};
+
+// Works out the sentence's mood, from the left wall's links if the sentence was link parsed,
+// and otherwise from the first word's tags. Punctuation decides questions and exclamations.
+pub fn sentence_mood(part: &SentenceParts) -> SentenceTypes {
+  let last_char = part
+    .corrected_sentence
+    .trim_end()
+    .chars()
+    .last();
+
+  // W: http://www.abisource.com/projects/link-grammar/dict/section-W.html
+  // Wi connects the left wall to the verb of an imperative, e.g. "Create a new folder".
+  let wall_w = part
+    .links
+    .get_left_wall()
+    .and_then(|lw| lw.get_disjunct(LinkTypes::W, ConnectorPointing::Right));
+
+  if last_char == Some('?') || part.links.is_question() {
+    return SentenceTypes::Interrogative;
+  }
+
+  let is_imperative = match wall_w {
+    Some(w_disjunct) => w_disjunct.has_subscript(vec!["i"]),
+    // Without a linkage, commands start with a verb in its base form.
+    None => part
+      .tokens
+      .first()
+      .map(|token| {
+        token_has_pos(token, TreebankPOS::VB) && WordnetVerbs::contains(&get_token_lemma(token))
+      })
+      .unwrap_or(false),
+  };
+
+  if is_imperative {
+    return SentenceTypes::Imperative;
+  }
+
+  if last_char == Some('!') {
+    return SentenceTypes::Exclamatory;
+  }
+
+  SentenceTypes::Declarative
+}
+
+// Sets the sentence's mood. Imperatives also get an implicit addressee agent, which becomes
+// the subject of the command's action if it doesn't have one.
+pub fn parse_mood(sema_sentence: &SemaSentence, part: &SentenceParts) -> Result<SemaSentence> {
+  let mut mood_sentence = sema_sentence.clone();
+
+  let mood = sentence_mood(part);
+
+  if mood == SentenceTypes::Imperative && !sema_sentence.has_addressee_agent() {
+    let mut symbol = Symbol::new(sema_sentence.max_symbol());
+    let addressee = Addressee::new(&mut symbol);
+
+    // The command's action is the first one, e.g. "buy" in "Buy milk to make pancakes".
+    if let Some(action) = mood_sentence
+      .actions
+      .first_mut()
+    {
+      let has_subject = action
+        .properties
+        .iter()
+        .any(|p| {
+          matches!(
+            p,
+            ActionProperties::Agent { .. } | ActionProperties::Experiencer { .. }
+          )
+        });
+
+      if !has_subject {
+        let verbnet_class = action
+          .verbnet_class
+          .as_deref()
+          .and_then(VerbNetClasses::get);
+
+        action
+          .properties
+          .insert(
+            0,
+            slot_property(verbnet_class, ArgumentSlots::Subject, &addressee.symbol, |agent| {
+              ActionProperties::Agent { agent }
+            }),
+          );
+      }
+    }
+
+    mood_sentence
+      .agents
+      .push(Agents::Addressee(addressee));
+
+    mood_sentence = assign_propbank_args(&mood_sentence);
+  }
+
+  mood_sentence.mood = Some(mood);
+
+  Ok(mood_sentence)
+}
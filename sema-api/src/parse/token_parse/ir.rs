@@ -19,12 +19,14 @@ pub struct IRCoordinatingConjunction {
 pub struct IRAction {
   pub token_index: usize,
   pub text: String,
+  pub lemma: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IREntity {
   pub token_index: usize,
   pub text: String,
+  pub lemma: String,
   pub plurality: Plurality,
 }
 
@@ -34,11 +36,18 @@ pub struct IRPerson {
   pub text: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IRPreposition {
+  pub token_index: usize,
+  pub text: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IR {
   CoordinatingConjunction(IRCoordinatingConjunction),
   Delimiter(IRDelimiter),
   Action(IRAction),
   Entity(IREntity),
-  Person(IRPerson)
+  Person(IRPerson),
+  Preposition(IRPreposition),
 }
\ No newline at end of file
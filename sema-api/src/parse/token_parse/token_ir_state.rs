@@ -2,6 +2,7 @@ use crate::nlp::{
   human_names::HumanNames, sentence_parts::SentenceParts, treebank_pos::TreebankPOS,
 };
 use super::ir::*;
+use crate::parse::link_parse::parse_negation::NEGATIVE_PRONOUNS;
 use crate::wordnet::wordnet_verbs::WordnetVerbs;
 use anyhow::{anyhow, Result};
 use nlprule::types::owned::Token;
@@ -23,15 +24,82 @@ pub fn get_token_text(token: &Token) -> String {
     .to_owned()
 }
 
+pub fn get_token_lemma(token: &Token) -> String {
+  let lemma = token.word.tags[0]
+    .lemma
+    .as_ref()
+    .to_lowercase();
+
+  if lemma.is_empty() {
+    get_token_text(token).to_lowercase()
+  } else {
+    lemma
+  }
+}
+
+// true if any of the token's tags has the pos, e.g. "watch" is tagged both NN:UN and VB.
+pub fn token_has_pos(token: &Token, pos: TreebankPOS) -> bool {
+  (0..token
+    .word
+    .tags
+    .len())
+    .any(|i| get_token_pos(token, i).map_or(false, |p| p == pos))
+}
+
+// Punctuation doesn't need to be parsed for the token parser to have understood a sentence.
+pub fn is_ignored_pos(pos: &TreebankPOS) -> bool {
+  matches!(pos, TreebankPOS::PCT | TreebankPOS::PERIOD | TreebankPOS::EMPTY)
+}
+
+// Negation needs the link parser to work out what it applies to, e.g. "coffee with no sugar".
+pub fn is_negation_token(token: &Token) -> bool {
+  let text = get_token_text(token).to_lowercase();
+
+  get_token_lemma(token) == "not"
+    || ["no", "without", "never"].contains(&text.as_str())
+    || NEGATIVE_PRONOUNS.contains(&text.as_str())
+}
+
 pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
   // println!("parse tokens");
   let mut output = input.clone(); // I don't think I need to clone here.
   let token = output.get_current_token();
-  let pos = get_token_pos(&token, 0)?;
+  // Tags the treebank list doesn't have (e.g. "SENT_START") are left for the link parser.
+  let pos = get_token_pos(&token, 0).unwrap_or(TreebankPOS::UNKNOWN);
   // println!("pos: {:?}", pos);
 
+  let ir_len = output.ir.len();
+
+  if is_negation_token(&token) {
+    output
+      .unhandled
+      .push(output.current_index);
+
+    return Ok(output);
+  }
+
+  // Commands start with a verb, even when the tagger's first guess is a noun, e.g. "book a flight".
+  if output.current_index == 0
+    && token_has_pos(&token, TreebankPOS::VB)
+    && WordnetVerbs::contains(&get_token_lemma(&token))
+  {
+    output.push_parsed_token(IR::Action(IRAction {
+      token_index: output.current_index,
+      text: get_token_text(&token),
+      lemma: get_token_lemma(&token),
+    }));
+
+    return Ok(output);
+  }
+
   match pos {
-    TreebankPOS::CC => {
+    TreebankPOS::CC | TreebankPOS::COMMA => {
+      output.push_parsed_token(IR::CoordinatingConjunction(IRCoordinatingConjunction {
+        text: get_token_text(&token),
+      }));
+    }
+    // The tagger gives commas PCT, but in a list they join its items: "eggs, milk and bread".
+    TreebankPOS::PCT if get_token_text(&token) == "," => {
       output.push_parsed_token(IR::CoordinatingConjunction(IRCoordinatingConjunction {
         text: get_token_text(&token),
       }));
@@ -44,7 +112,12 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
     }
     TreebankPOS::EX => (),
     TreebankPOS::FW => (),
-    TreebankPOS::IN => (),
+    TreebankPOS::IN => {
+      output.push_parsed_token(IR::Preposition(IRPreposition {
+        token_index: output.current_index,
+        text: get_token_text(&token).to_lowercase(),
+      }));
+    }
     TreebankPOS::JJ => (),
     TreebankPOS::JJR => (),
     TreebankPOS::JJS => (),
@@ -54,6 +127,7 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
       output.push_parsed_token(IR::Entity(IREntity {
         token_index: output.current_index,
         text: get_token_text(&token),
+        lemma: get_token_lemma(&token),
         plurality: Plurality::Singular,
       }));
     }
@@ -61,6 +135,7 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
       output.push_parsed_token(IR::Entity(IREntity {
         token_index: output.current_index,
         text: get_token_text(&token),
+        lemma: get_token_lemma(&token),
         plurality: Plurality::Plural,
       }));
     }
@@ -69,30 +144,19 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
       output.push_parsed_token(IR::Entity(IREntity {
         token_index: output.current_index,
         text: get_token_text(&token),
+        lemma: get_token_lemma(&token),
         plurality: Plurality::Unknown,
       }));
     }
     TreebankPOS::NNUN => {
-      // case: "watch a movie"
-      // NNUN should be an action if:
-      // 1. This is the first token
-      // 2. the second tag is "VB" (verb)
-      if output.current_index == 0
-        && token
-          .word
-          .tags
-          .len()
-          > 2
-      {
-        let second_pos = get_token_pos(&token, 1)?;
-
-        if second_pos == TreebankPOS::VB {
-          output.push_parsed_token(IR::Action(IRAction {
-            token_index: output.current_index,
-            text: get_token_text(&token),
-          }));
-        }
-      }
+      // case: "buy milk"
+      // A first token that can also be a verb ("watch a movie") was already made an action above.
+      output.push_parsed_token(IR::Entity(IREntity {
+        token_index: output.current_index,
+        text: get_token_text(&token),
+        lemma: get_token_lemma(&token),
+        plurality: Plurality::Unknown,
+      }));
     }
     TreebankPOS::NNP => {
       // Proper Noun, singular. ex: "John", Denver, NORAD
@@ -119,11 +183,17 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
     TreebankPOS::RBSENT => (),
     TreebankPOS::RP => (),
     TreebankPOS::SYM => (),
-    TreebankPOS::TO => (),
+    TreebankPOS::TO => {
+      output.push_parsed_token(IR::Preposition(IRPreposition {
+        token_index: output.current_index,
+        text: get_token_text(&token).to_lowercase(),
+      }));
+    }
     TreebankPOS::UH => (),
     TreebankPOS::VB => output.push_parsed_token(IR::Action(IRAction {
       token_index: output.current_index,
       text: get_token_text(&token),
+      lemma: get_token_lemma(&token),
     })),
     TreebankPOS::VBD => (),
     TreebankPOS::VBG => (),
@@ -136,7 +206,6 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
     TreebankPOS::WRB => (),
     TreebankPOS::EMPTY => (),
     TreebankPOS::UNKNOWN => (),
-    TreebankPOS::COMMA => (),
     TreebankPOS::SemiColon => (),
     TreebankPOS::PERIOD => (),
     TreebankPOS::DollarSign => (),
@@ -144,6 +213,13 @@ pub fn tokens_to_ir(input: TokenIRState) -> Result<TokenIRState> {
     TreebankPOS::QuestionMark => (),
   }
 
+  // Nothing came of the token, so the IR doesn't cover the whole sentence.
+  if output.ir.len() == ir_len && !is_ignored_pos(&pos) {
+    output
+      .unhandled
+      .push(output.current_index);
+  }
+
   Ok(output)
 }
 
@@ -152,6 +228,9 @@ pub struct TokenIRState {
   pub part: SentenceParts,
   pub current_index: usize,
   pub ir: Vec<IR>,
+
+  // indexes of tokens that didn't become IR
+  pub unhandled: Vec<usize>,
 }
 
 impl TokenIRState {
@@ -160,12 +239,14 @@ impl TokenIRState {
       part: part.clone(),
       current_index: 0,
       ir: vec![],
+      unhandled: vec![],
     };
 
     if part.tokens.len() == 1 && WordnetVerbs::contains(&get_token_text(&part.tokens[0])) {
       token_ir_state.push_parsed_token(IR::Action(IRAction {
         token_index: 0,
         text: get_token_text(&part.tokens[0]),
+        lemma: get_token_lemma(&part.tokens[0]),
       }));
       return Ok(token_ir_state);
     } else {
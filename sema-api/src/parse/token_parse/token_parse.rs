@@ -1,274 +1,339 @@
 use super::ir::*;
-use super::token_ir_state::TokenIRState;
+use super::token_ir_state::{get_token_pos, get_token_text, is_ignored_pos, TokenIRState};
 
 use anyhow::Result;
 
 use crate::nlp::sentence_parts::SentenceParts;
+use crate::nlp::human_names::HumanNames;
+use crate::nlp::person_names::PersonName;
+use crate::parse::link_parse::{
+  link_parse::slot_property,
+  parse_args::assign_propbank_args,
+  parse_coordination::{add_coordination, COLLECTIVE_CONJUNCTIONS},
+};
+use crate::propbank::propbank_frames::PropBankFrames;
 use crate::sema::{
   action::{Action, ActionProperties},
   agents::*,
-  entity::Entity,
+  contact::{is_email, is_url},
+  entity::{Entity, EntityProperties, Quantities},
   sema_sentence::SemaSentence,
   symbol::Symbol,
 };
-
+use crate::verbnet::verbnet_classes::{ArgumentSlots, VerbNetClasses};
+
+// Longer inputs go to the link parser.
+pub const MAX_TOKEN_PARSE_WORDS: usize = 6;
+
+// The token parser handles short commands and fragments the link parser found no linkage for,
+// e.g. "Email Jane the report". It only handles a sentence when:
+// - it has at most MAX_TOKEN_PARSE_WORDS words,
+// - duckling found nothing in it (times, numbers, emails, etc. need the link parser),
+// - every word became IR (questions, pronouns, adjectives, negations and past tense verbs don't),
+// - the IR matches one of the patterns below.
+// Otherwise it returns None.
 pub fn parse_with_tokens(part: SentenceParts) -> Result<Option<SemaSentence>> {
+  if !is_token_parse_candidate(&part) {
+    return Ok(None);
+  }
+
   let token_ir_state: TokenIRState = TokenIRState::new(part.clone())?;
 
+  if !token_ir_state
+    .unhandled
+    .is_empty()
+  {
+    return Ok(None);
+  }
+
   let mut symbol = Symbol::new(0);
 
   let parse_attempt = match &token_ir_state.ir[..] {
-    // "create"
-    [IR::Action(action)] => {
-      println!("{:?}", action);
-
-      let action = Action::new(action.text.clone(), &mut symbol);
-
-      let mut sentence = SemaSentence::new();
-      sentence
-        .actions
-        .push(action);
-
-      Some(sentence)
-    }
-
     // "Mary"
     [IR::Person(ir_person)] => {
-      let mut person = Person::new(&mut symbol);
-
-      person
-        .properties
-        .push(PersonProperties::Name {
-          name: ir_person
-            .text
-            .clone(),
-        });
+      let person = new_person(&[ir_person], &mut symbol);
 
       let mut sentence = SemaSentence::new();
       sentence
         .agents
         .push(Agents::Person(person));
 
-        Some(sentence)
+      Some(sentence)
     }
 
     // Mary Jane (first and last name)
     [IR::Person(ir_person_1), IR::Person(ir_person_2)] => {
-      let mut person = Person::new(&mut symbol);
-
-      person
-        .properties
-        .push(PersonProperties::FirstName {
-          first_name: ir_person_1
-            .text
-            .clone(),
-        });
-      person
-        .properties
-        .push(PersonProperties::LastName {
-          last_name: ir_person_2
-            .text
-            .clone(),
-        });
+      let person = new_person(&[ir_person_1, ir_person_2], &mut symbol);
 
       let mut sentence = SemaSentence::new();
       sentence
         .agents
         .push(Agents::Person(person));
 
-        Some(sentence)
+      Some(sentence)
     }
-    // "create folder"
-    [IR::Action(action), IR::Entity(parse_entity)] => {
-      println!("got a match");
 
-      let action = Action::new(action.text.clone(), &mut symbol);
+    // "create", "buy milk and eggs", "buy Jane a house", "create a picture for Mary"
+    [IR::Action(ir_action), rest @ ..] => parse_command(ir_action, rest, &mut symbol),
 
-      let entity = Entity::new(
-        parse_entity
-          .text
-          .clone(),
-        &mut symbol,
-      );
+    _ => None,
+  };
 
-      let mut sentence = SemaSentence::new();
+  match parse_attempt {
+    Some(sentence) => {
+      let sentence = assign_propbank_args(&sentence);
 
-      sentence
-        .actions
-        .push(action);
+      Ok(Some(sentence))
+    }
+    None => Ok(None),
+  }
+}
 
-      sentence
-        .entities
-        .push(entity);
+pub fn is_token_parse_candidate(part: &SentenceParts) -> bool {
+  let words = part
+    .tokens
+    .iter()
+    .filter(|token| {
+      get_token_pos(token, 0)
+        .map(|pos| !is_ignored_pos(&pos))
+        .unwrap_or(true)
+    })
+    .count();
+
+  // emails and urls duckling didn't find
+  let has_contact = part
+    .tokens
+    .iter()
+    .map(get_token_text)
+    .any(|text| is_email(&text) || is_url(&text));
+
+  words > 0 && words <= MAX_TOKEN_PARSE_WORDS && part.duck.parts.is_empty() && !has_contact
+}
 
-      Some(sentence)
-    }
-    // "create a folder"
-    [IR::Action(parse_action), IR::Delimiter(_delim), IR::Entity(parse_entity)] => {
-      println!("got a match");
-
-      let action = Action::new(
-        parse_action
-          .text
-          .clone(),
-        &mut symbol,
-      );
-
-      let entity = Entity::new(
-        parse_entity
-          .text
-          .clone(),
-        &mut symbol,
-      );
+// An argument of a command, before symbols are given out.
+enum CommandArg<'a> {
+  Entities(Vec<&'a IREntity>), // a compound noun, e.g. "ice cream"
+  Person(Vec<&'a IRPerson>),
+}
 
-      let mut sentence = SemaSentence::new();
+// The IR after the verb:
+//   [Person] [object list] [Preposition (Person | object)]
+// where an object list is objects separated by "and", "or" or commas, and an object is an
+// optional determiner followed by one or more nouns.
+fn parse_command(ir_action: &IRAction, rest: &[IR], symbol: &mut Symbol) -> Option<SemaSentence> {
+  let mut i = 0;
 
-      sentence
-        .actions
-        .push(action);
+  // "buy Jane a house"
+  let mut indirect_object = None;
 
-      sentence
-        .entities
-        .push(entity);
+  if let Some(IR::Person(_)) = rest.get(i) {
+    let (person, next) = take_person(rest, i);
 
-      Some(sentence)
+    if next < rest.len() {
+      indirect_object = Some(person);
+      i = next;
     }
+  }
 
-    // Buy Jane a house
-    [IR::Action(ir_action), IR::Person(ir_person), IR::Delimiter(_delim_1), IR::Entity(ir_entity)] =>
-    {
-      println!("Buy Jane a house");
-
-      let mut action = Action::new(
-        ir_action
-          .text
-          .clone(),
-        &mut symbol,
-      );
-
-      let mut person = Person::new(&mut symbol);
-      person
-        .properties
-        .push(PersonProperties::Name {
-          name: ir_person
-            .text
-            .clone(),
-        });
-
-      let entity = Entity::new(
-        ir_entity
-          .text
-          .clone(),
-        &mut symbol,
-      );
-
-      action
-        .properties
-        .push(ActionProperties::Patient {
-          patient: entity
-            .symbol
-            .clone(),
-        });
+  let mut objects = vec![];
+  let mut conjunction = None;
 
-      let mut sentence = SemaSentence::new();
+  while let Some((object, next)) = take_object(rest, i) {
+    objects.push(object);
+    i = next;
 
-      sentence
-        .actions
-        .push(action);
+    match rest.get(i) {
+      Some(IR::CoordinatingConjunction(c)) => {
+        conjunction = Some(c.text.to_lowercase());
+        i += 1
+      }
+      _ => break,
+    }
+  }
 
-      sentence
-        .entities
-        .push(entity);
+  // "for Mary", "to the office"
+  let mut prepositional = None;
 
-      sentence
-        .agents
-        .push(Agents::Person(person));
+  if let Some(IR::Preposition(preposition)) = rest.get(i) {
+    let (object, next) = take_object(rest, i + 1)?;
 
-      Some(sentence)
-    }
+    prepositional = Some((preposition.text.clone(), object));
+    i = next;
+  }
 
-    // create a picture for Mary
-    // Note: Person needs to be capitalized for now.
-    [IR::Action(ir_action), IR::Delimiter(_ir_delim_1), IR::Entity(ir_entity), IR::CoordinatingConjunction(_cc_1), IR::Person(ir_person)] =>
-    {
-      println!("got a match");
-
-      let action = Action::new(
-        ir_action
-          .text
-          .clone(),
-        &mut symbol,
-      );
-
-      let entity = Entity::new(
-        ir_entity
-          .text
-          .clone(),
-        &mut symbol,
-      );
-
-      let mut person = Person::new(&mut symbol);
-
-      person
-        .properties
-        .push(PersonProperties::Name {
-          name: ir_person
-            .text
-            .clone(),
-        });
+  // Anything left over means the sentence has a shape this parser doesn't know.
+  if i != rest.len() {
+    return None;
+  }
 
-      let mut sentence = SemaSentence::new();
+  let has_object = !objects.is_empty();
+  let verbnet_class = VerbNetClasses::classify(&ir_action.lemma, has_object);
 
-      sentence
-        .actions
-        .push(action);
+  let mut action = Action::new(ir_action.lemma.clone(), symbol);
+  action.verbnet_class = verbnet_class.map(|class| class.id.clone());
+  action.roleset = PropBankFrames::pick_roleset(&action.action_type, action.verbnet_class.as_deref())
+    .map(|roleset| roleset.id.clone());
 
-      sentence
-        .entities
-        .push(entity);
+  let mut sentence = SemaSentence::new();
+
+  if let Some(person) = indirect_object {
+    let s = add_command_arg(&mut sentence, person, symbol);
+
+    action
+      .properties
+      .push(slot_property(verbnet_class, ArgumentSlots::IndirectObject, &s, |benefactive| {
+        ActionProperties::Benefactive { benefactive }
+      }));
+  }
+
+  let mut object_symbols = vec![];
+
+  for object in objects {
+    let s = add_command_arg(&mut sentence, object, symbol);
+
+    action
+      .properties
+      .push(slot_property(verbnet_class, ArgumentSlots::Object, &s, |patient| {
+        ActionProperties::Patient { patient }
+      }));
+
+    object_symbols.push(s);
+  }
+
+  // "buy eggs, milk and bread"
+  let is_collective = conjunction
+    .map(|c| COLLECTIVE_CONJUNCTIONS.contains(&c.as_str()))
+    .unwrap_or(false);
+
+  if is_collective {
+    add_coordination(&mut sentence, object_symbols, symbol);
+  }
+
+  if let Some((preposition, object)) = prepositional {
+    let s = add_command_arg(&mut sentence, object, symbol);
+
+    action
+      .properties
+      .push(slot_property(
+        verbnet_class,
+        ArgumentSlots::Prepositional(preposition),
+        &s,
+        |recipient| ActionProperties::Recipient { recipient },
+      ));
+  }
+
+  sentence
+    .actions
+    .push(action);
+
+  Some(sentence)
+}
+
+fn take_person(ir: &[IR], start: usize) -> (CommandArg<'_>, usize) {
+  let mut people = vec![];
+  let mut i = start;
+
+  while let Some(IR::Person(ir_person)) = ir.get(i) {
+    people.push(ir_person);
+    i += 1;
+  }
+
+  (CommandArg::Person(people), i)
+}
+
+fn take_object(ir: &[IR], start: usize) -> Option<(CommandArg<'_>, usize)> {
+  let mut i = start;
+
+  if let Some(IR::Person(_)) = ir.get(i) {
+    return Some(take_person(ir, i));
+  }
+
+  if let Some(IR::Delimiter(_)) = ir.get(i) {
+    i += 1;
+  }
+
+  let mut entities = vec![];
+
+  while let Some(IR::Entity(ir_entity)) = ir.get(i) {
+    entities.push(ir_entity);
+    i += 1;
+  }
+
+  if entities.is_empty() {
+    return None;
+  }
+
+  Some((CommandArg::Entities(entities), i))
+}
+
+// Adds the agent or entity for the argument, and returns its symbol.
+fn add_command_arg(sentence: &mut SemaSentence, arg: CommandArg, symbol: &mut Symbol) -> String {
+  match arg {
+    CommandArg::Person(people) => {
+      let person = new_person(&people, symbol);
+      let s = person
+        .symbol
+        .clone();
 
       sentence
         .agents
         .push(Agents::Person(person));
 
-      Some(sentence)
+      s
+    }
+    CommandArg::Entities(entities) => {
+      // Compound nouns are joined the same way as the link parser, e.g. "ice_cream".
+      let entity_key = entities
+        .iter()
+        .map(|e| e.lemma.clone())
+        .collect::<Vec<String>>()
+        .join("_");
+
+      let mut entity = Entity::new(entity_key, symbol);
+
+      if let Some(last) = entities.last() {
+        if matches!(last.plurality, Plurality::Plural) {
+          entity
+            .properties
+            .push(EntityProperties::Quantity {
+              quantity: Quantities::Multiple,
+            });
+        }
+      }
+
+      let s = entity
+        .symbol
+        .clone();
+
+      sentence
+        .entities
+        .push(entity);
+
+      s
     }
+  }
+}
 
-    _ => {
-      None
-      // let sentence = SemaSentence::new();
+fn new_person(people: &[&IRPerson], symbol: &mut Symbol) -> Person {
+  let mut person = Person::new(symbol);
 
-      // let verified_sema_sentence_json =
-      //   verify_and_repair_sema_json(sentence, part).await?;
+  let names = people
+    .iter()
+    .map(|p| p.text.clone())
+    .collect::<Vec<String>>();
 
-      // // dbg!(&verified_sema_sentence_json);
+  person
+    .properties
+    .extend(PersonName::parse(&names).properties());
 
-      // // b.1.json
-      // verified_sema_sentence_json
-    }
-    // _ => {
-    //   let a = get_ml_generated_sentence(vec![part
-    //     .original_sentence
-    //     .clone()])
-    //   .await?;
-    //   // assuming only one sentence is returned via this manner.
-    //   let b = a
-    //     .into_iter()
-    //     .next()
-    //     .ok_or("no sentence returned")
-    //     .map_err(|e| anyhow::anyhow!(e))?;
-
-    //   let sema_sentence_json = b.1.json.clone();
-
-    //   let verified_sema_sentence_json =
-    //     verify_and_repair_sema_json(sema_sentence_json.clone(), part).await?;
-
-    //   dbg!(&verified_sema_sentence_json);
-
-    //   // b.1.json
-    //   verified_sema_sentence_json
-    // }
-  };
+  if let Some(gender) = people
+    .first()
+    .and_then(|p| HumanNames::gender(&p.text))
+  {
+    person
+      .properties
+      .push(PersonProperties::Gender { gender });
+  }
 
-  Ok(parse_attempt)
+  person
 }
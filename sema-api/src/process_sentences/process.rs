@@ -1,10 +1,15 @@
 use anyhow::Result;
 
+use crate::frames::action_frames::apply_action_frames;
 use crate::nlp::sentence_parts::SentenceParts;
-use crate::parse::link_parse::parse_with_links;
-// use crate::parse::token_parse::parse_with_tokens;
+use crate::parse::link_parse::{parse_tense::is_imperative_verb, parse_with_links};
+use crate::parse::mood::parse_mood;
+use crate::parse::token_parse::parse_with_tokens;
 use crate::sema::sema_sentence::SemaSentence;
 use crate::services::sema_ai::get_ml_generated_sentence;
+use crate::verify::verify::verify_and_repair_sema_json;
+
+use link_parser_rust_bindings::lp::{disjunct::ConnectorPointing, link_types::LinkTypes};
 
 pub async fn process_parts(parts: Vec<SentenceParts>) -> Result<Vec<SemaSentence>> {
   let mut sema_sentences = Vec::new();
@@ -18,14 +23,28 @@ pub async fn process_parts(parts: Vec<SentenceParts>) -> Result<Vec<SemaSentence
 }
 
 pub async fn process_part(part: SentenceParts) -> Result<SemaSentence> {
-  // First attempt is to use the token parser.
-  // if let Some(s) = parse_with_tokens(part.clone())? {
-  //   println!("parsed with tokens");
-  //   dbg!(&s);
-  //   return Ok(s);
-  // }
-
-  // Second attempt is to use the link parser.
+  let sema_sentence = parse_part(&part).await?;
+
+  let sema_sentence = parse_mood(&sema_sentence, &part)?;
+
+  let sema_sentence = apply_action_frames(&sema_sentence)?;
+
+  // Whichever backend made it, check the output against the sentence parts.
+  verify_and_repair_sema_json(&sema_sentence, &part)
+}
+
+async fn parse_part(part: &SentenceParts) -> Result<SemaSentence> {
+  // Short commands the link parser found no linkage for are parsed from their tokens, e.g.
+  // "Email Jane the report". Anything with a linkage goes to the link parser, whose output also
+  // has tense, negation, modifiers and the srl cross check.
+  if !has_linkage(part) {
+    if let Some(s) = parse_with_tokens(part.clone())? {
+      log::debug!("parsed with tokens: {}", part.corrected_sentence);
+      return Ok(s);
+    }
+  }
+
+  // Otherwise the link parser.
   if let Some(s) = parse_with_links(part.clone())? {
     return Ok(s);
   }
@@ -45,3 +64,16 @@ pub async fn process_part(part: SentenceParts) -> Result<SemaSentence> {
   // Ok(sema_sentence)
   Ok(b.1.json)
 }
+
+// Whether the link parser found a linkage for the sentence. A command whose verb has an inverted
+// subject doesn't count, e.g. "milk and bread" in "Buy eggs, milk and bread".
+fn has_linkage(part: &SentenceParts) -> bool {
+  let words = &part
+    .links
+    .words;
+
+  !words.is_empty()
+    && !words
+      .iter()
+      .any(|w| is_imperative_verb(w) && w.has_disjunct(LinkTypes::SI, ConnectorPointing::Right))
+}
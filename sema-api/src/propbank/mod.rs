@@ -0,0 +1,7 @@
+pub mod propbank_frames;
+
+use self::propbank_frames::PropBankFrames;
+
+pub fn init_propbank_cells(data_path: &str) {
+  PropBankFrames::init(data_path);
+}
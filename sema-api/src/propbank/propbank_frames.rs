@@ -0,0 +1,250 @@
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+use std::fs;
+
+use once_cell::sync::OnceCell;
+
+use crate::sema::action::{ArgMMods, ArgMods, Args};
+
+// A numbered argument of a roleset, e.g. ARG0 "baker" (PAG).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolesetRole {
+  pub n: String, // "0" to "5", or "m" for modifiers
+
+  pub f: String, // function tag, e.g. "PAG"
+
+  pub descr: String,
+
+  // (verbnet class, verbnet thematic role), e.g. ("build-26.1", "agent")
+  pub verbnet_roles: Vec<(String, String)>,
+}
+
+impl RolesetRole {
+  pub fn to_arg(&self) -> Args {
+    let function_tag = ArgMods::from_str(&self.f);
+
+    match self
+      .n
+      .to_uppercase()
+      .as_str()
+    {
+      "0" => Args::ARG0(function_tag),
+      "1" => Args::ARG1(function_tag),
+      "2" => Args::ARG2(function_tag),
+      "3" => Args::ARG3(function_tag),
+      "4" => Args::ARG4(function_tag),
+      "M" => Args::ARGM(ArgMMods::from_str(&self.f)),
+      _ => Args::Unknown,
+    }
+  }
+
+  pub fn has_verbnet_role(&self, verbnet_class: &str, thematic_role: &str) -> bool {
+    self
+      .verbnet_roles
+      .iter()
+      .any(|(class, role)| {
+        verbnet_class_matches(class, verbnet_class) && role.eq_ignore_ascii_case(thematic_role)
+      })
+  }
+}
+
+// Older frame files only have the class number, e.g. "26.1" for "build-26.1".
+fn verbnet_class_matches(linked_class: &str, verbnet_class: &str) -> bool {
+  linked_class == verbnet_class || verbnet_class.ends_with(&format!("-{}", linked_class))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Roleset {
+  pub id: String, // e.g. "bake.01"
+
+  pub name: String,
+
+  pub roles: Vec<RolesetRole>,
+}
+
+impl Roleset {
+  pub fn links_verbnet_class(&self, verbnet_class: &str) -> bool {
+    self
+      .roles
+      .iter()
+      .any(|role| {
+        role
+          .verbnet_roles
+          .iter()
+          .any(|(class, _)| verbnet_class_matches(class, verbnet_class))
+      })
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct PropBankFrames {
+  // predicate lemma -> rolesets, in the order they appear in the frame file
+  rolesets: HashMap<String, Vec<Roleset>>,
+}
+
+impl PropBankFrames {
+  // Loads every frame file (e.g. "bake.xml") in <data_path>/propbank.
+  pub fn new(data_path: &str) -> Result<Self> {
+    let dir = format!("{}/propbank", data_path);
+    let mut rolesets: HashMap<String, Vec<Roleset>> = HashMap::new();
+
+    for entry in fs::read_dir(&dir)? {
+      let path = entry?.path();
+
+      if path
+        .extension()
+        .map(|ext| ext != "xml")
+        .unwrap_or(true)
+      {
+        continue;
+      }
+
+      let xml = fs::read_to_string(&path)?;
+
+      for (lemma, predicate_rolesets) in parse_frame_file(&xml)
+        .map_err(|err| anyhow!("Unable to parse propbank frame file {:?}: {}", path, err))?
+      {
+        rolesets
+          .entry(lemma)
+          .or_insert(vec![])
+          .extend(predicate_rolesets);
+      }
+    }
+
+    Ok(PropBankFrames { rolesets })
+  }
+
+  pub fn init(data_path: &str) {
+    let propbank_frames = PropBankFrames::new(data_path).expect("Unable to create PropBankFrames instance");
+    PROPBANK_FRAMES
+      .set(propbank_frames)
+      .expect("Unable to set PROPBANK_FRAMES");
+  }
+
+  pub fn get_rolesets(lemma: &str) -> Vec<&'static Roleset> {
+    let propbank_frames = PROPBANK_FRAMES
+      .get()
+      .expect("PROPBANK_FRAMES is not initialized");
+
+    propbank_frames
+      .rolesets
+      .get(&lemma.to_lowercase())
+      .map(|rolesets| rolesets.iter().collect())
+      .unwrap_or_default()
+  }
+
+  pub fn get_roleset(roleset_id: &str) -> Option<&'static Roleset> {
+    let lemma = roleset_id
+      .split('.')
+      .next()?;
+
+    PropBankFrames::get_rolesets(lemma)
+      .into_iter()
+      .find(|roleset| roleset.id == roleset_id)
+  }
+
+  // Without word senses, prefer a roleset linked to the verb's VerbNet class,
+  // and otherwise take the first (usually most common) roleset.
+  pub fn pick_roleset(lemma: &str, verbnet_class: Option<&str>) -> Option<&'static Roleset> {
+    let rolesets = PropBankFrames::get_rolesets(lemma);
+
+    if let Some(verbnet_class) = verbnet_class {
+      if let Some(roleset) = rolesets
+        .iter()
+        .find(|roleset| roleset.links_verbnet_class(verbnet_class))
+      {
+        return Some(roleset);
+      }
+    }
+
+    rolesets
+      .first()
+      .copied()
+  }
+}
+
+// Frame files look like:
+// <frameset>
+//   <predicate lemma="bake">
+//     <roleset id="bake.01" name="cook">
+//       <roles>
+//         <role descr="baker" f="PAG" n="0">
+//           <rolelinks><rolelink class="build-26.1" resource="VerbNet">agent</rolelink></rolelinks>
+//         </role>
+// Older frame files use <vnrole vncls="26.1" vntheta="agent"/> instead of rolelinks.
+fn parse_frame_file(xml: &str) -> Result<Vec<(String, Vec<Roleset>)>> {
+  let doc = roxmltree::Document::parse(xml)?;
+
+  let mut predicates = vec![];
+
+  for predicate in doc
+    .descendants()
+    .filter(|n| n.has_tag_name("predicate"))
+  {
+    let lemma = match predicate.attribute("lemma") {
+      Some(lemma) => lemma.to_lowercase(),
+      None => continue,
+    };
+
+    let mut rolesets = vec![];
+
+    for roleset in predicate
+      .children()
+      .filter(|n| n.has_tag_name("roleset"))
+    {
+      let roles = roleset
+        .descendants()
+        .filter(|n| n.has_tag_name("role"))
+        .map(|role| {
+          let mut verbnet_roles = vec![];
+
+          for link in role.descendants() {
+            if link.has_tag_name("rolelink") && link.attribute("resource") == Some("VerbNet") {
+              if let (Some(class), Some(theta)) = (link.attribute("class"), link.text()) {
+                verbnet_roles.push((class.to_string(), theta.trim().to_lowercase()));
+              }
+            } else if link.has_tag_name("vnrole") {
+              if let (Some(class), Some(theta)) = (link.attribute("vncls"), link.attribute("vntheta")) {
+                verbnet_roles.push((class.to_string(), theta.to_lowercase()));
+              }
+            }
+          }
+
+          RolesetRole {
+            n: role
+              .attribute("n")
+              .unwrap_or_default()
+              .to_string(),
+            f: role
+              .attribute("f")
+              .unwrap_or_default()
+              .to_string(),
+            descr: role
+              .attribute("descr")
+              .unwrap_or_default()
+              .to_string(),
+            verbnet_roles,
+          }
+        })
+        .collect::<Vec<RolesetRole>>();
+
+      rolesets.push(Roleset {
+        id: roleset
+          .attribute("id")
+          .unwrap_or_default()
+          .to_string(),
+        name: roleset
+          .attribute("name")
+          .unwrap_or_default()
+          .to_string(),
+        roles,
+      });
+    }
+
+    predicates.push((lemma, rolesets));
+  }
+
+  Ok(predicates)
+}
+
+pub static PROPBANK_FRAMES: OnceCell<PropBankFrames> = OnceCell::new();
@@ -1,21 +1,32 @@
 extern crate serde_derive;
 extern crate serde_json;
 
+pub mod results;
+
 use anyhow::{self, Result};
 use derive_more::Display;
 use futures::lock::Mutex;
 use futures::TryFutureExt;
 
+use schemars::JsonSchema;
 use serde_json::json;
 
 use std::sync::Arc;
+use std::time::Instant;
 
+use crate::built_info::engine_version;
+use crate::config::server_config;
+use crate::middleware::auth::AuthSubject;
 use crate::process_sentences::process::process_parts;
+use crate::schema::{openapi_document, schema_by_name, SCHEMA_VERSION};
+use crate::sema::sema_sentence::SemaSentence;
 use crate::services::allennlp_service::{get_semantic_role_labels, SRLResponse};
 use crate::services::duckling::duckling_parse_sentence;
+use crate::state::State;
 
 use actix_web::{get, post, web, Error, HttpResponse, Responder};
 use link_parser_rust_bindings::{LinkParser, LinkParserError};
+use sema_api_db::db::parse_result::{insert_parse_result, NewParseResult};
 
 use crate::nlp::sentence_parts::{SentenceEncodings, SentenceParts, SentenceText};
 
@@ -45,13 +56,21 @@ impl From<LinkParserError> for SemaAPiError {
   }
 }
 
+impl From<serde_json::Error> for SemaAPiError {
+  fn from(err: serde_json::Error) -> Self {
+    SemaAPiError { err: err.into() }
+  }
+}
+
 #[get("/health")]
 async fn health() -> Result<impl Responder, Error> {
   Ok(HttpResponse::Ok())
 }
 
 #[post("/srl")]
-async fn srl(payload: String) -> Result<impl Responder, Error> {
+async fn srl(payload: String, subject: web::ReqData<AuthSubject>) -> Result<impl Responder, Error> {
+  log::info!("srl request from subject: {}", subject.subject);
+
   let srl_resp: SRLResponse = get_semantic_role_labels(&payload)
     .map_err(SemaAPiError::from)
     .await?;
@@ -59,7 +78,22 @@ async fn srl(payload: String) -> Result<impl Responder, Error> {
   Ok(HttpResponse::Ok().json(srl_resp))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[get("/schema/{name}")]
+async fn json_schema(name: web::Path<String>) -> Result<impl Responder, Error> {
+  match schema_by_name(&name) {
+    Some(root_schema) => Ok(HttpResponse::Ok().json(root_schema)),
+    None => Ok(HttpResponse::NotFound().json(json!({
+      "error": format!("unknown schema: {}", name),
+    }))),
+  }
+}
+
+#[get("/openapi.json")]
+async fn openapi_spec() -> Result<impl Responder, Error> {
+  Ok(HttpResponse::Ok().json(openapi_document()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TextToJSONRequestObject {
   pub sentences: Vec<String>,
   pub parts: Option<bool>,
@@ -67,11 +101,73 @@ pub struct TextToJSONRequestObject {
   pub encoding: Option<SentenceEncodings>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TextToJSONResponse {
+  pub schema_version: String,
+
+  pub sema_sentences: Vec<SemaSentence>,
+
+  // Only included when the request sets `parts: true`. Debug output, so not part of the schema.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  #[schemars(with = "Option<Vec<serde_json::Value>>")]
+  pub parts: Option<Vec<SentenceParts>>,
+}
+
 #[post("/text-to-json")]
 async fn text_to_json(
   payload: web::Json<TextToJSONRequestObject>,
   link_parser: web::Data<Arc<Mutex<LinkParser>>>,
+  subject: web::ReqData<AuthSubject>,
+  state: Option<web::Data<State>>,
 ) -> Result<impl Responder, Error> {
+  log::info!("text-to-json request from subject: {}", subject.subject);
+
+  let started = Instant::now();
+
+  let (all_parts, sema_sentences) = run_text_to_json(&payload, &link_parser).await?;
+
+  if let Some(state) = state {
+    let duration_ms = started
+      .elapsed()
+      .as_millis() as i64;
+
+    // Storing is best effort, a failed insert shouldn't fail the parse.
+    if let Err(err) = store_parse_result(
+      &state,
+      &subject.subject,
+      &payload,
+      &all_parts,
+      &sema_sentences,
+      duration_ms,
+    )
+    .await
+    {
+      log::warn!("unable to store parse result: {:?}", err);
+    }
+  }
+
+  let parts = if payload
+    .parts
+    .unwrap_or(false)
+  {
+    Some(all_parts)
+  } else {
+    None
+  };
+
+  let resp = TextToJSONResponse {
+    schema_version: SCHEMA_VERSION.to_string(),
+    sema_sentences,
+    parts,
+  };
+
+  Ok(HttpResponse::Ok().json(resp))
+}
+
+pub async fn run_text_to_json(
+  payload: &TextToJSONRequestObject,
+  link_parser: &Arc<Mutex<LinkParser>>,
+) -> Result<(Vec<SentenceParts>, Vec<SemaSentence>), SemaAPiError> {
   let lp = link_parser
     .lock()
     .await;
@@ -90,44 +186,62 @@ async fn text_to_json(
     .sentences
     .iter()
   {
-    let sentence_text =
-      SentenceText::new(sentence.clone(), encoding.clone(), repair).map_err(SemaAPiError::from)?;
+    let sentence_text = SentenceText::new(sentence.clone(), encoding.clone(), repair)?;
 
-    let mut parts = SentenceParts::from_text(&sentence_text).map_err(SemaAPiError::from)?;
+    let mut parts = SentenceParts::from_text(&sentence_text)?;
 
-    if let Some(links) = lp
-      .parse_sentence(&sentence_text.text())
-      .map_err(SemaAPiError::from)?
-    {
+    // The linkage decides whether the link parser or the token parser handles the sentence.
+    if let Some(links) = lp.parse_sentence(sentence_text.text())? {
       parts.links = links;
     }
 
-    let duckling_parts = duckling_parse_sentence(sentence_text.text())
-      .await
-      .map_err(SemaAPiError::from)?;
+    let duckling_parts = duckling_parse_sentence(sentence_text.text()).await?;
 
     parts.duck = duckling_parts.into();
 
+    if server_config().srl_cross_check {
+      // Optional stage, so a failed srl call shouldn't fail the parse.
+      match get_semantic_role_labels(sentence_text.text()).await {
+        Ok(srl_resp) => parts.srl = Some(srl_resp),
+        Err(err) => log::warn!("unable to get semantic role labels: {:?}", err),
+      }
+    }
+
     all_parts.push(parts);
   }
 
-  let sema_sentences = process_parts(all_parts.clone())
-    .await
-    .map_err(SemaAPiError::from)?;
+  let sema_sentences = process_parts(all_parts.clone()).await?;
 
-  let resp = if payload
-    .parts
-    .unwrap_or(false)
-  {
-    json!({
-      "sema_sentences": sema_sentences,
-      "parts": &all_parts,
-    })
-  } else {
-    json!({
-      "sema_sentences": sema_sentences,
-    })
+  Ok((all_parts, sema_sentences))
+}
+
+async fn store_parse_result(
+  state: &State,
+  subject: &str,
+  payload: &TextToJSONRequestObject,
+  all_parts: &[SentenceParts],
+  sema_sentences: &[SemaSentence],
+  duration_ms: i64,
+) -> anyhow::Result<()> {
+  let parts_summary = all_parts
+    .iter()
+    .map(|parts| parts.summary())
+    .collect::<Vec<_>>();
+
+  let new_parse_result = NewParseResult {
+    subject: subject.to_string(),
+    input_text: payload
+      .sentences
+      .clone(),
+    request: serde_json::to_value(payload)?,
+    parts_summary: serde_json::to_value(parts_summary)?,
+    sema_sentences: serde_json::to_value(sema_sentences)?,
+    engine_version: engine_version(),
+    schema_version: SCHEMA_VERSION.to_string(),
+    duration_ms,
   };
 
-  Ok(HttpResponse::Ok().json(resp))
+  insert_parse_result(&state.pool, new_parse_result).await?;
+
+  Ok(())
 }
@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use futures::lock::Mutex;
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use actix_web::{get, post, web, Error, HttpResponse, Responder};
+use link_parser_rust_bindings::LinkParser;
+use sema_api_db::db::parse_result::{get_parse_result_by_id, list_parse_results, DBParseResult};
+
+use super::{run_text_to_json, SemaAPiError, TextToJSONRequestObject, TextToJSONResponse};
+use crate::config::server_config;
+use crate::middleware::auth::AuthSubject;
+use crate::schema::SCHEMA_VERSION;
+use crate::state::State;
+
+const DEFAULT_LIST_LIMIT: i64 = 50;
+const MAX_LIST_LIMIT: i64 = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListResultsQuery {
+  pub subject: Option<String>,
+  pub after: Option<DateTime<Utc>>,
+  pub before: Option<DateTime<Utc>>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerunResponse {
+  pub stored: DBParseResult,
+
+  pub current: TextToJSONResponse,
+
+  // true if the current engine's sema_sentences differ from the stored ones
+  pub changed: bool,
+}
+
+fn persistence_disabled() -> HttpResponse {
+  HttpResponse::NotFound().json(json!({
+    "error": "result persistence is not enabled",
+  }))
+}
+
+fn result_not_found(id: &Uuid) -> HttpResponse {
+  HttpResponse::NotFound().json(json!({
+    "error": format!("no result found for id: {}", id),
+  }))
+}
+
+fn other_subject_forbidden() -> HttpResponse {
+  HttpResponse::Forbidden().json(json!({
+    "error": format!(
+      "missing required scope for other subjects' results: {}",
+      server_config().results_admin_scope
+    ),
+  }))
+}
+
+// Callers only see their own results, unless they have the results admin scope.
+fn can_read(caller: &AuthSubject, result_subject: &str) -> bool {
+  caller.subject == result_subject || caller.has_scope(&server_config().results_admin_scope)
+}
+
+#[get("/results/{id}")]
+async fn get_result(
+  id: web::Path<Uuid>,
+  caller: web::ReqData<AuthSubject>,
+  state: Option<web::Data<State>>,
+) -> Result<impl Responder, Error> {
+  let state = match state {
+    Some(state) => state,
+    None => return Ok(persistence_disabled()),
+  };
+
+  let parse_result = get_parse_result_by_id(&state.pool, *id)
+    .await
+    .map_err(SemaAPiError::from)?;
+
+  match parse_result {
+    Some(parse_result) if can_read(&caller, &parse_result.subject) => {
+      Ok(HttpResponse::Ok().json(parse_result))
+    }
+    Some(_) => Ok(other_subject_forbidden()),
+    None => Ok(result_not_found(&id)),
+  }
+}
+
+#[get("/results")]
+async fn list_results(
+  query: web::Query<ListResultsQuery>,
+  caller: web::ReqData<AuthSubject>,
+  state: Option<web::Data<State>>,
+) -> Result<impl Responder, Error> {
+  let state = match state {
+    Some(state) => state,
+    None => return Ok(persistence_disabled()),
+  };
+
+  let is_admin = caller.has_scope(&server_config().results_admin_scope);
+
+  // Without the admin scope, results are always filtered to the caller
+  let subject = match query
+    .subject
+    .as_deref()
+  {
+    Some(subject) if subject == caller.subject || is_admin => Some(subject),
+    Some(_) => return Ok(other_subject_forbidden()),
+    None if is_admin => None,
+    None => Some(
+      caller
+        .subject
+        .as_str(),
+    ),
+  };
+
+  let limit = query
+    .limit
+    .unwrap_or(DEFAULT_LIST_LIMIT)
+    .clamp(1, MAX_LIST_LIMIT);
+
+  let parse_results = list_parse_results(&state.pool, subject, query.after, query.before, limit)
+    .await
+    .map_err(SemaAPiError::from)?;
+
+  Ok(HttpResponse::Ok().json(parse_results))
+}
+
+// Runs a stored request against the current engine, for finding regressions on real traffic.
+// The re-run itself is not stored.
+#[post("/results/{id}/rerun")]
+async fn rerun_result(
+  id: web::Path<Uuid>,
+  link_parser: web::Data<Arc<Mutex<LinkParser>>>,
+  caller: web::ReqData<AuthSubject>,
+  state: Option<web::Data<State>>,
+) -> Result<impl Responder, Error> {
+  let state = match state {
+    Some(state) => state,
+    None => return Ok(persistence_disabled()),
+  };
+
+  let stored = match get_parse_result_by_id(&state.pool, *id)
+    .await
+    .map_err(SemaAPiError::from)?
+  {
+    Some(stored) => stored,
+    None => return Ok(result_not_found(&id)),
+  };
+
+  if !can_read(&caller, &stored.subject) {
+    return Ok(other_subject_forbidden());
+  }
+
+  let payload: TextToJSONRequestObject = serde_json::from_value(
+    stored
+      .request
+      .clone(),
+  )
+  .map_err(SemaAPiError::from)?;
+
+  let (all_parts, sema_sentences) = run_text_to_json(&payload, &link_parser).await?;
+
+  let changed =
+    serde_json::to_value(&sema_sentences).map_err(SemaAPiError::from)? != stored.sema_sentences;
+
+  let parts = if payload
+    .parts
+    .unwrap_or(false)
+  {
+    Some(all_parts)
+  } else {
+    None
+  };
+
+  let current = TextToJSONResponse {
+    schema_version: SCHEMA_VERSION.to_string(),
+    sema_sentences,
+    parts,
+  };
+
+  Ok(HttpResponse::Ok().json(RerunResponse {
+    stored,
+    current,
+    changed,
+  }))
+}
@@ -0,0 +1,167 @@
+use schemars::{gen::SchemaSettings, schema::RootSchema, schema_for};
+use serde_json::{json, Value};
+
+use crate::routes::{TextToJSONRequestObject, TextToJSONResponse};
+use crate::sema::sema_sentence::SemaSentence;
+use crate::services::allennlp_service::SRLResponse;
+
+// Returned with every /text-to-json response.
+// Bump the major version for any breaking change to the SemaSentence shape
+// (renamed/removed fields, changed property variants), minor for additions.
+pub const SCHEMA_VERSION: &str = "4.0.0";
+
+pub const SCHEMA_NAMES: [&str; 4] = [
+  "sema_sentence",
+  "text_to_json_request",
+  "text_to_json_response",
+  "srl_response",
+];
+
+pub fn schema_by_name(name: &str) -> Option<RootSchema> {
+  match name {
+    "sema_sentence" => Some(schema_for!(SemaSentence)),
+    "text_to_json_request" => Some(schema_for!(TextToJSONRequestObject)),
+    "text_to_json_response" => Some(schema_for!(TextToJSONResponse)),
+    "srl_response" => Some(schema_for!(SRLResponse)),
+    _ => None,
+  }
+}
+
+pub fn openapi_document() -> Value {
+  let mut generator = SchemaSettings::openapi3().into_generator();
+
+  let request_schema = generator.subschema_for::<TextToJSONRequestObject>();
+  let response_schema = generator.subschema_for::<TextToJSONResponse>();
+  let srl_response_schema = generator.subschema_for::<SRLResponse>();
+
+  let schemas = generator.take_definitions();
+
+  json!({
+    "openapi": "3.0.3",
+    "info": {
+      "title": "Sematic NLU API",
+      "version": SCHEMA_VERSION,
+    },
+    "paths": {
+      "/text-to-json": {
+        "post": {
+          "requestBody": {
+            "required": true,
+            "content": {
+              "application/json": { "schema": request_schema },
+            },
+          },
+          "responses": {
+            "200": {
+              "description": "One SemaSentence per input sentence",
+              "content": {
+                "application/json": { "schema": response_schema },
+              },
+            },
+          },
+        },
+      },
+      "/srl": {
+        "post": {
+          "requestBody": {
+            "required": true,
+            "content": {
+              "text/plain": { "schema": { "type": "string" } },
+            },
+          },
+          "responses": {
+            "200": {
+              "description": "Semantic role labels for the sentence",
+              "content": {
+                "application/json": { "schema": srl_response_schema },
+              },
+            },
+          },
+        },
+      },
+      "/schema/{name}": {
+        "get": {
+          "parameters": [{
+            "name": "name",
+            "in": "path",
+            "required": true,
+            "schema": {
+              "type": "string",
+              "enum": SCHEMA_NAMES,
+            },
+          }],
+          "responses": {
+            "200": { "description": "JSON Schema for the named type" },
+            "404": { "description": "Unknown schema name" },
+          },
+        },
+      },
+      "/results": {
+        "get": {
+          "parameters": [
+            { "name": "subject", "in": "query", "schema": { "type": "string" } },
+            { "name": "after", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+            { "name": "before", "in": "query", "schema": { "type": "string", "format": "date-time" } },
+            { "name": "limit", "in": "query", "schema": { "type": "integer", "minimum": 1, "maximum": 500 } },
+          ],
+          "responses": {
+            "200": { "description": "Stored parse results, newest first" },
+            "403": { "description": "Another subject's results without the results admin scope" },
+            "404": { "description": "Result persistence is not enabled" },
+          },
+        },
+      },
+      "/results/{id}": {
+        "get": {
+          "parameters": [{
+            "name": "id",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string", "format": "uuid" },
+          }],
+          "responses": {
+            "200": { "description": "A stored parse result" },
+            "403": { "description": "Another subject's result without the results admin scope" },
+            "404": { "description": "Unknown id, or result persistence is not enabled" },
+          },
+        },
+      },
+      "/results/{id}/rerun": {
+        "post": {
+          "parameters": [{
+            "name": "id",
+            "in": "path",
+            "required": true,
+            "schema": { "type": "string", "format": "uuid" },
+          }],
+          "responses": {
+            "200": { "description": "The stored result, the current engine's output, and whether they differ" },
+            "403": { "description": "Another subject's result without the results admin scope" },
+            "404": { "description": "Unknown id, or result persistence is not enabled" },
+          },
+        },
+      },
+      "/openapi.json": {
+        "get": {
+          "responses": {
+            "200": { "description": "This document" },
+          },
+        },
+      },
+      "/health": {
+        "get": {
+          "responses": {
+            "200": { "description": "Server is up" },
+          },
+        },
+      },
+    },
+    "components": {
+      "schemas": schemas,
+      "securitySchemes": {
+        "bearerAuth": { "type": "http", "scheme": "bearer" },
+      },
+    },
+    "security": [{ "bearerAuth": [] }],
+  })
+}
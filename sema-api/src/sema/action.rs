@@ -1,6 +1,8 @@
-use super::{location::Directions, symbol::Symbol};
+use schemars::JsonSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::{location::Directions, symbol::Symbol, temporal::Tenses};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum Args {
   V,
   ARG0(ArgMods),
@@ -14,7 +16,7 @@ pub enum Args {
 }
 
 // More deets at: https://verbs.colorado.edu/~mpalmer/projects/ace/PBguidelines.pdf
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum ArgMods {
   None,
   // Proto-agent: 
@@ -35,6 +37,7 @@ pub enum ArgMods {
   PRP, // purpose
   VSP, // Verbs specific
   TMP, // Temporal, but this may be a mistake in the propbank data
+  EXT, // extent
   COM, // Comitative
   LOC, // Location
   CAU, // Cause
@@ -53,6 +56,7 @@ impl ArgMods {
       "PRP" => ArgMods::PRP,
       "VSP" => ArgMods::VSP,
       "TMP" => ArgMods::TMP,
+      "EXT" => ArgMods::EXT,
       "COM" => ArgMods::COM,
       "LOC" => ArgMods::LOC,
       "CAU" => ArgMods::CAU,
@@ -61,7 +65,7 @@ impl ArgMods {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum ArgMMods {
   COM, // comitative, indicate accompaniment (with another agent)
   LOC, // locative
@@ -112,20 +116,126 @@ impl ArgMMods {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// VerbNet thematic roles. More deets at: https://verbs.colorado.edu/verb-index/VerbNet_Guidelines.pdf
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub enum ThematicRoles {
+  Agent,
+  CoAgent,
+  Patient,
+  Theme,
+  CoTheme,
+  Experiencer,
+  Stimulus,
+  Recipient,
+  Beneficiary,
+  Instrument,
+  Source,
+  Destination,
+  Goal,
+  Location,
+  Topic,
+  Product,
+  Result,
+  Material,
+  Attribute,
+  Asset,
+  Cause,
+  Pivot,
+  Extent,
+  Unknown,
+}
+
+impl ThematicRoles {
+  pub fn from_str(s: &str) -> Self {
+    match s
+      .to_lowercase()
+      .replace(['-', '_'], "")
+      .as_str()
+    {
+      "agent" => ThematicRoles::Agent,
+      "coagent" => ThematicRoles::CoAgent,
+      "patient" | "copatient" => ThematicRoles::Patient,
+      "theme" => ThematicRoles::Theme,
+      "cotheme" => ThematicRoles::CoTheme,
+      "experiencer" => ThematicRoles::Experiencer,
+      "stimulus" => ThematicRoles::Stimulus,
+      "recipient" => ThematicRoles::Recipient,
+      "beneficiary" => ThematicRoles::Beneficiary,
+      "instrument" => ThematicRoles::Instrument,
+      "source" | "initiallocation" | "initialstate" => ThematicRoles::Source,
+      "destination" => ThematicRoles::Destination,
+      "goal" => ThematicRoles::Goal,
+      "location" => ThematicRoles::Location,
+      "topic" => ThematicRoles::Topic,
+      "product" => ThematicRoles::Product,
+      "result" => ThematicRoles::Result,
+      "material" => ThematicRoles::Material,
+      "attribute" | "value" => ThematicRoles::Attribute,
+      "asset" => ThematicRoles::Asset,
+      "cause" => ThematicRoles::Cause,
+      "pivot" => ThematicRoles::Pivot,
+      "extent" => ThematicRoles::Extent,
+      _ => ThematicRoles::Unknown,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Action {
   pub action_type: String, // propbank predicates.roleset.id for now...
 
   pub symbol: String, // maybe this should be "symbol"? 
 
+  pub verbnet_class: Option<String>, // e.g. "give-13.1"
+
+  pub roleset: Option<String>, // propbank roleset id, e.g. "bake.01"
+
+  // Numbered propbank arguments with their function tags.
+  // `properties` has the same arguments as agent/patient/etc. for convenience.
+  #[serde(default)]
+  pub args: Vec<ActionArg>,
+
+  // The domain action frame the action was mapped onto, if one matched.
+  #[serde(default)]
+  pub frame: Option<ActionFrameMatch>,
+
   pub properties: Vec<ActionProperties>,
 }
 
+// e.g. { "arg": { "ARG0": "PAG" }, "symbol": "$1" }
+// Some modifiers, like ARGM-NEG, don't point at another object, so they have no symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActionArg {
+  pub arg: Args,
+
+  pub symbol: Option<String>,
+}
+
+// e.g. { "name": "buy", "slots": [{ "slot": "buyer", "symbols": ["$1"] }] }
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActionFrameMatch {
+  pub name: String,
+
+  pub slots: Vec<FrameSlotFill>,
+}
+
+// Empty symbols means nothing in the sentence filled the slot.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FrameSlotFill {
+  pub slot: String,
+
+  pub symbols: Vec<String>,
+}
+
 impl Action {
   pub fn new(action_type: String, symbol: &mut Symbol) -> Self {
     Self {
       action_type,
       symbol: symbol.next_symbol(),
+      verbnet_class: None,
+      roleset: None,
+      args: Vec::new(),
+      frame: None,
       properties: Vec::new(),
     }
   }
@@ -135,6 +245,10 @@ impl Action {
     Action {
       action_type: l.clone(),
       symbol: format!("${l}"),
+      verbnet_class: None,
+      roleset: None,
+      args: vec![],
+      frame: None,
       properties: vec![],
     }
   }
@@ -153,7 +267,7 @@ Arg4 - ending point
 ArgM - modfier
 */
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "action_property_type")]
@@ -167,5 +281,112 @@ pub enum ActionProperties {
   Direction { direction: Directions },
   Attribute { attribute: String },
   Purpose { purpose: String }, // symbol to an action or event
+  Proposition { proposition: String }, // symbol to an embedded clause, "he said he was sorry"
   Negate { negate: bool },
+  Theme { theme: String },
+  CoAgent { co_agent: String },
+  Experiencer { experiencer: String },
+  Stimulus { stimulus: String },
+  Source { source: String },
+  Destination { destination: String },
+  Location { location: String },
+  Topic { topic: String },
+  Tense { tense: Tenses },
+  Aspect { aspect: Aspects },
+  Modality { modality: Modalities },
+  Voice { voice: Voices },
+  Modifier {
+    modifier_type: AdverbCategories, // category (manner)
+    modifier: Option<String>,        // actual language used (gently)
+    amplifiers: Vec<String>,         // (very) carefully
+  },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AdverbCategories {
+  Manner,    // "gently", "quickly"
+  Degree,    // "barely", "completely"
+  Frequency, // "twice", "often"
+  Certainty, // "probably", "definitely"
+}
+
+// Simple aspect isn't given, e.g. "I call John".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Aspects {
+  Progressive, // "I am calling John"
+  Perfect,     // "I have called John"
+  Habitual,    // "I used to call John", "I usually call John"
+}
+
+// Active voice isn't given, e.g. "the dog chased the cat".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Voices {
+  Passive, // "the cat was chased by the dog", "he got fired"
+}
+
+// The modal verb in front of the action, e.g. "should" in "I should have called John".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Modalities {
+  Can,
+  Could,
+  May,
+  Might,
+  Must,
+  Shall,
+  Should,
+  Will,
+  Would,
+}
+
+impl Modalities {
+  pub fn from_str(s: &str) -> Option<Self> {
+    match s.to_lowercase().as_str() {
+      "can" | "can't" | "cannot" => Some(Modalities::Can),
+      "could" | "couldn't" => Some(Modalities::Could),
+      "may" => Some(Modalities::May),
+      "might" => Some(Modalities::Might),
+      "must" | "mustn't" => Some(Modalities::Must),
+      "shall" | "shan't" => Some(Modalities::Shall),
+      "should" | "shouldn't" => Some(Modalities::Should),
+      "will" | "won't" | "'ll" => Some(Modalities::Will),
+      "would" | "wouldn't" | "'d" => Some(Modalities::Would),
+      _ => None,
+    }
+  }
+}
+
+impl ActionProperties {
+  // Builds the property for an argument from its VerbNet thematic role.
+  pub fn from_thematic_role(role: &ThematicRoles, symbol: String) -> Option<Self> {
+    match role {
+      ThematicRoles::Agent | ThematicRoles::Cause | ThematicRoles::Pivot => {
+        Some(ActionProperties::Agent { agent: symbol })
+      }
+      ThematicRoles::CoAgent => Some(ActionProperties::CoAgent { co_agent: symbol }),
+      ThematicRoles::Patient => Some(ActionProperties::Patient { patient: symbol }),
+      ThematicRoles::Theme | ThematicRoles::CoTheme => Some(ActionProperties::Theme { theme: symbol }),
+      ThematicRoles::Experiencer => Some(ActionProperties::Experiencer { experiencer: symbol }),
+      ThematicRoles::Stimulus => Some(ActionProperties::Stimulus { stimulus: symbol }),
+      ThematicRoles::Recipient => Some(ActionProperties::Recipient { recipient: symbol }),
+      ThematicRoles::Beneficiary => Some(ActionProperties::Benefactive {
+        benefactive: symbol,
+      }),
+      ThematicRoles::Instrument => Some(ActionProperties::Instrument { instrument: symbol }),
+      ThematicRoles::Source | ThematicRoles::Material => Some(ActionProperties::Source { source: symbol }),
+      ThematicRoles::Destination | ThematicRoles::Goal => Some(ActionProperties::Destination {
+        destination: symbol,
+      }),
+      ThematicRoles::Location => Some(ActionProperties::Location { location: symbol }),
+      ThematicRoles::Topic => Some(ActionProperties::Topic { topic: symbol }),
+      ThematicRoles::Product | ThematicRoles::Result => Some(ActionProperties::Outcome { outcome: symbol }),
+      ThematicRoles::Attribute | ThematicRoles::Asset | ThematicRoles::Extent => {
+        Some(ActionProperties::Attribute { attribute: symbol })
+      }
+      ThematicRoles::Unknown => None,
+    }
+  }
 }
\ No newline at end of file
@@ -1,15 +1,17 @@
 use convert_case::{Case, Casing};
+use schemars::JsonSchema;
 
 use std::string::ToString;
 use strum_macros::Display;
 
 use super::symbol::Symbol;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "agent_type")]
 pub enum Agents {
   Ego(Ego),
+  Addressee(Addressee),
   Subject(Subject),
   Person(Person),
   Group(Group),
@@ -21,6 +23,7 @@ impl Agents {
   pub fn get_symbol(&self) -> String {
     match self {
       Self::Ego(ego) => ego.symbol.to_owned(),
+      Self::Addressee(addressee) => addressee.symbol.to_owned(),
       Self::Subject(subject) => subject.symbol.to_owned(),
       Self::Person(p) => p.symbol.to_owned(),
       Self::Group(g) => g.symbol.to_owned(),
@@ -34,9 +37,16 @@ impl Agents {
       _ => false,
     }
   }
+
+  pub fn is_addressee(&self) -> bool {
+    match self {
+      Self::Addressee(_) => true,
+      _ => false,
+    }
+  }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Ego {
   // #[dummy(faker = "FakeString(COMMANDING_AGT_symbol)")]
   pub symbol: String,
@@ -52,7 +62,24 @@ impl Ego {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Who a command is for, e.g. the assistant in "Create a new folder".
+// Imperatives don't name them, so they're added implicitly.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Addressee {
+  pub symbol: String,
+  pub properties: Vec<AgentProperties>,
+}
+
+impl Addressee {
+  pub fn new(symbol: &mut Symbol) -> Self {
+    Self {
+      symbol: symbol.next_symbol(),
+      properties: vec![],
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Subject {
   pub symbol: String,
   pub properties: Vec<AgentProperties>,
@@ -67,7 +94,7 @@ impl Subject {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "agent_property_type")]
@@ -89,7 +116,7 @@ impl AgentProperties {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Person {
   pub symbol: String,
   pub properties: Vec<PersonProperties>,
@@ -104,7 +131,7 @@ impl Person {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "agent_property_type")]
@@ -112,11 +139,21 @@ pub enum PersonProperties {
   // Subject is for targeting. Basically the opposite of "Ego"
   Subject { subject: bool },
   Name { name: String },
+  Title { title: String },
   FirstName { first_name: String },
+  MiddleName { middle_name: String },
   LastName { last_name: String },
+  Suffix { suffix: String },
   Email { email: String },
   PhoneNumber { phone_number: String },
+  Url { url: String },
   Address { address: String },
+  Description { description: String },
+  Occupation { occupation: String },
+  Nationality { nationality: String },
+  Age { age: u32 },
+  Kinship { kinship: String },
+  Restriction { restriction: String }, // symbol to the action of a relative clause
   Modifier {
     modifier_type: String, // category (chromatic)
     modifier: Option<String>, // actual language used (colored)
@@ -127,7 +164,7 @@ pub enum PersonProperties {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Genders {
   #[strum(serialize = "male")]
@@ -147,11 +184,21 @@ impl PersonProperties {
     match pp {
       PersonProperties::Subject { subject } => subject.to_string(),
       PersonProperties::Name { name } => name.to_owned(),
+      PersonProperties::Title { title } => title.to_owned(),
       PersonProperties::FirstName { first_name } => first_name.to_owned(),
+      PersonProperties::MiddleName { middle_name } => middle_name.to_owned(),
       PersonProperties::LastName { last_name } => last_name.to_owned(),
+      PersonProperties::Suffix { suffix } => suffix.to_owned(),
       PersonProperties::Email { email } => email.to_owned(),
       PersonProperties::PhoneNumber { phone_number } => phone_number.to_owned(),
+      PersonProperties::Url { url } => url.to_owned(),
       PersonProperties::Address { address } => address.to_owned(),
+      PersonProperties::Description { description } => description.to_owned(),
+      PersonProperties::Occupation { occupation } => occupation.to_owned(),
+      PersonProperties::Nationality { nationality } => nationality.to_owned(),
+      PersonProperties::Age { age } => age.to_string(),
+      PersonProperties::Kinship { kinship } => kinship.to_owned(),
+      PersonProperties::Restriction { restriction } => restriction.to_owned(),
       PersonProperties::Modifier {
         modifier_type,
         modifier,
@@ -182,13 +229,22 @@ impl PersonProperties {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Group {
   pub symbol: String,
   pub properties: Vec<GroupProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Group {
+  pub fn new(symbol: &mut Symbol) -> Self {
+    Self {
+      symbol: symbol.next_symbol(),
+      properties: vec![],
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "group_property_type")]
@@ -198,13 +254,22 @@ pub enum GroupProperties {
   Location { location: String },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Company {
   pub symbol: String,
   pub properties: Vec<CompanyProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Company {
+  pub fn new(symbol: &mut Symbol) -> Self {
+    Self {
+      symbol: symbol.next_symbol(),
+      properties: vec![],
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "company_property_type")]
@@ -212,6 +277,8 @@ pub enum CompanyProperties {
   Name { name: String },
   PhoneNumber { phone_number: String },
   Email { email: String },
+  Url { url: String },
   Industry { industry: String },
   Profession { profession: String },
+  Restriction { restriction: String }, // symbol to the action of a relative clause
 }
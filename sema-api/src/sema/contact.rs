@@ -0,0 +1,174 @@
+use schemars::JsonSchema;
+
+use super::{
+  agents::{CompanyProperties, PersonProperties},
+  symbol::Symbol,
+};
+
+// Contact details that don't belong to an agent, e.g. "Send the invoice to billing@acme.com".
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Contact {
+  pub symbol: String,
+  pub properties: Vec<ContactProperties>,
+}
+
+impl Contact {
+  pub fn new(symbol: &mut Symbol, property: ContactProperties) -> Self {
+    Self {
+      symbol: symbol.next_symbol(),
+      properties: vec![property],
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[serde(untagged)]
+pub enum ContactProperties {
+  Email { email: String },
+  PhoneNumber { phone_number: String },
+  Url { url: String },
+}
+
+impl ContactProperties {
+  pub fn email(text: &str) -> Self {
+    Self::Email {
+      email: normalize_email(text),
+    }
+  }
+
+  pub fn phone_number(text: &str) -> Self {
+    Self::PhoneNumber {
+      phone_number: normalize_phone_number(text),
+    }
+  }
+
+  pub fn url(text: &str) -> Self {
+    Self::Url {
+      url: normalize_url(text),
+    }
+  }
+
+  // "acme.com" for "sales@acme.com" or "https://www.acme.com/about". Phone numbers have none.
+  pub fn domain(&self) -> Option<&str> {
+    match self {
+      Self::Email { email } => email
+        .split_once('@')
+        .map(|(_, domain)| domain),
+      Self::Url { url } => {
+        let host = url
+          .split_once("://")
+          .map(|(_, rest)| rest)
+          .unwrap_or(url)
+          .split('/')
+          .next()?;
+
+        Some(
+          host
+            .strip_prefix("www.")
+            .unwrap_or(host),
+        )
+      }
+      Self::PhoneNumber { .. } => None,
+    }
+  }
+}
+
+impl From<ContactProperties> for PersonProperties {
+  fn from(contact: ContactProperties) -> Self {
+    match contact {
+      ContactProperties::Email { email } => PersonProperties::Email { email },
+      ContactProperties::PhoneNumber { phone_number } => {
+        PersonProperties::PhoneNumber { phone_number }
+      }
+      ContactProperties::Url { url } => PersonProperties::Url { url },
+    }
+  }
+}
+
+impl From<ContactProperties> for CompanyProperties {
+  fn from(contact: ContactProperties) -> Self {
+    match contact {
+      ContactProperties::Email { email } => CompanyProperties::Email { email },
+      ContactProperties::PhoneNumber { phone_number } => {
+        CompanyProperties::PhoneNumber { phone_number }
+      }
+      ContactProperties::Url { url } => CompanyProperties::Url { url },
+    }
+  }
+}
+
+pub fn is_email(text: &str) -> bool {
+  match text.split_once('@') {
+    Some((local, domain)) => {
+      !local.is_empty()
+        && !domain.contains('@')
+        && domain
+          .trim_end_matches('.')
+          .contains('.')
+    }
+    None => false,
+  }
+}
+
+pub fn is_url(text: &str) -> bool {
+  let text = text.to_lowercase();
+
+  text.starts_with("http://") || text.starts_with("https://") || text.starts_with("www.")
+}
+
+fn trim_punctuation(text: &str) -> &str {
+  text
+    .trim()
+    .trim_end_matches(|c| ".,;:!?)".contains(c))
+}
+
+// "Sales@Acme.com" -> "sales@acme.com"
+pub fn normalize_email(text: &str) -> String {
+  let email = trim_punctuation(text);
+
+  email
+    .strip_prefix("mailto:")
+    .unwrap_or(email)
+    .to_lowercase()
+}
+
+// E.164, e.g. "(+1) 650-555-1234" -> "+16505551234". Numbers without a country code are taken to
+// be North American when they have 10 digits. Shorter local numbers, like "555-1234", can't be
+// E.164 and are kept as their digits.
+pub fn normalize_phone_number(text: &str) -> String {
+  let digits = text
+    .chars()
+    .filter(|c| c.is_ascii_digit())
+    .collect::<String>();
+
+  if text.contains('+') {
+    format!("+{}", digits)
+  } else if digits.len() == 10 {
+    format!("+1{}", digits)
+  } else if digits.len() == 11 && digits.starts_with('1') {
+    format!("+{}", digits)
+  } else {
+    digits
+  }
+}
+
+// The scheme and host are lowercased. The path is case sensitive, so it's kept as it is.
+pub fn normalize_url(text: &str) -> String {
+  let url = trim_punctuation(text);
+
+  let (scheme, rest) = match url.split_once("://") {
+    Some((scheme, rest)) => (Some(scheme), rest),
+    None => (None, url),
+  };
+
+  let (host, path) = match rest.find('/') {
+    Some(i) => rest.split_at(i),
+    None => (rest, ""),
+  };
+
+  match scheme {
+    Some(scheme) => format!("{}://{}{}", scheme.to_lowercase(), host.to_lowercase(), path),
+    None => format!("{}{}", host.to_lowercase(), path),
+  }
+}
@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+
+// Notes about how a SemaSentence was built, e.g. roles that SRL disagreed with.
+// The output is still usable, but diagnostics say where it might be wrong or incomplete.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Diagnostic {
+  pub stage: DiagnosticStages,
+
+  pub kind: DiagnosticKinds,
+
+  pub message: String,
+
+  pub symbols: Vec<String>, // the objects the diagnostic is about, e.g. ["$2", "$4"]
+}
+
+impl Diagnostic {
+  pub fn new(stage: DiagnosticStages, kind: DiagnosticKinds, message: String, symbols: Vec<String>) -> Self {
+    Self {
+      stage,
+      kind,
+      message,
+      symbols,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticStages {
+  Srl,
+  Verify,
+  Frames,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKinds {
+  Repaired,     // something missing was filled in
+  Disagreement, // two sources gave different answers, the original was kept
+  Missing,      // something in the sentence isn't in the output, and couldn't be filled in
+  Removed,      // something broken was taken out, e.g. a property pointing to a missing symbol
+  Invalid,      // something was found, but doesn't fit where it was put, e.g. a slot of the wrong type
+}
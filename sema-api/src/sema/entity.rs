@@ -1,8 +1,9 @@
 use convert_case::{Case, Casing};
+use schemars::JsonSchema;
 
-use super::symbol::Symbol;
+use super::{measurement::Measurement, symbol::Symbol};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Entity {
   pub entity_type: String,
 
@@ -27,7 +28,7 @@ impl Entity {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 // #[serde(tag = "entity_property_type")]
 #[serde(untagged)]
@@ -41,9 +42,13 @@ pub enum EntityProperties {
   Quantity { quantity: Quantities },
   Occurance { occurs: String }, // symbol to temporal
   Attribute { attribute: String },
+  Negate { negate: bool }, // "no sugar", "without milk"
+  Measurement { measurement: Measurement }, // "5 kg", "$12.50", "two cups"
+  Members { members: Vec<String> }, // symbols of the entities in a list, "eggs, milk and bread"
+  Restriction { restriction: String }, // symbol to a relative clause, "the invoice John sent"
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Quantities {
   All,
@@ -1,8 +1,9 @@
 use convert_case::{Casing, Case};
+use schemars::JsonSchema;
 
-use super::{symbol::Symbol, temporal::{Temporals, Tense}};
+use super::{symbol::Symbol, temporal::Tense};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Event {
   pub event_type: String,
 
@@ -26,7 +27,7 @@ impl Event {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 // #[serde(tag = "event_property_type")]
 #[serde(untagged)]
@@ -34,12 +35,8 @@ pub enum EventProperties {
   Action { action: String },
   Occurance { occurs: String }, // symbol to temporal
   Duration { duration: String },  // symbol to temporal that is of type duration
+  Recurrence { recurs: String }, // symbol to temporal that is of type interval
   Tense { tense: Tense },
+  Negate { negate: bool }, // the event's action didn't, or won't, happen
   Location { location: String } // might want to give location as a property for actions too. 
 }
-
-pub enum Interval {
-  Once,
-  Repeating(Temporals),
-  // start and end?
-}
\ No newline at end of file
@@ -1,4 +1,5 @@
 use strum_macros::{self, Display};
+use schemars::JsonSchema;
 use super::symbol::Symbol;
 
 /*
@@ -8,7 +9,7 @@ Need to handles:
 - "behind his head "
  */
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "location_type")]
 pub enum Locations {
@@ -31,7 +32,7 @@ impl Locations {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Country {
   pub symbol: String,
   pub properties: Vec<CountryProperties>,
@@ -39,7 +40,7 @@ pub struct Country {
 
 // Relative locations
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Relative {
   pub symbol: String,
   pub properties: Vec<RelativeProperties>,
@@ -54,7 +55,7 @@ impl Relative {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "relative_location_type")]
@@ -76,7 +77,7 @@ pub enum RelativeProperties {
   },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum RelativeLocationTypes {
   #[strum(serialize = "at")]
@@ -109,7 +110,7 @@ pub enum RelativeLocationTypes {
 
 // Physical locations
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "country_property_type")]
@@ -118,13 +119,13 @@ pub enum CountryProperties {
   CountryCode { country_code: String },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct City {
   pub symbol: String,
   pub properties: Vec<CityProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "city_property_type")]
@@ -134,13 +135,13 @@ pub enum CityProperties {
   // CitySuffix { city_suffix: String },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LatLong {
   pub symbol: String,
   pub properties: Vec<LatLongProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "lat_long_property_type")]
@@ -150,14 +151,14 @@ pub enum LatLongProperties {
   LatLong { latitude: String, longitude: String },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Direction {
   pub symbol: String,
   pub direction: Directions,
   pub amplifiers: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Directions {
   Left,
@@ -191,3 +192,26 @@ pub enum Directions {
   RightBack,
   RightFront,
 }
+
+impl Directions {
+  // Single word directions, e.g. "left" in "turn left".
+  pub fn from_str(s: &str) -> Option<Self> {
+    match s.to_lowercase().as_ref() {
+      "left" => Some(Directions::Left),
+      "right" => Some(Directions::Right),
+      "up" | "upward" | "upwards" => Some(Directions::Up),
+      "down" | "downward" | "downwards" => Some(Directions::Down),
+      "back" | "backward" | "backwards" => Some(Directions::Back),
+      "forward" | "forwards" | "ahead" => Some(Directions::Front),
+      "north" => Some(Directions::North),
+      "south" => Some(Directions::South),
+      "east" => Some(Directions::East),
+      "west" => Some(Directions::West),
+      "northeast" => Some(Directions::Northeast),
+      "northwest" => Some(Directions::Northwest),
+      "southeast" => Some(Directions::Southeast),
+      "southwest" => Some(Directions::Southwest),
+      _ => None,
+    }
+  }
+}
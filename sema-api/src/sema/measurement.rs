@@ -0,0 +1,168 @@
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Measurement {
+  pub dimension: Dimensions,
+
+  pub value: f64,
+
+  pub unit: String, // as written or as duckling names it, e.g. "cup", "kg", "$"
+
+  // The value in the SI unit of the dimension: kg, m, m3 or K.
+  // Money is in the main unit of its currency, e.g. 0.5 for "50 cents".
+  pub si_value: Option<f64>,
+
+  pub si_unit: Option<String>,
+
+  pub currency: Option<String>, // ISO 4217, e.g. "USD"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Dimensions {
+  Mass,
+  Volume,
+  Distance,
+  Temperature,
+  Money,
+  Quantity, // units without a conversion, e.g. "bowl"
+}
+
+// How to convert a unit to SI: si_value = value * scale + offset.
+struct Conversion {
+  dimension: Dimensions,
+  si_unit: &'static str,
+  scale: f64,
+  offset: f64,
+}
+
+impl Measurement {
+  pub fn new(value: f64, unit: &str) -> Self {
+    match conversion(unit) {
+      Some(c) => Self {
+        dimension: c.dimension,
+        value,
+        unit: unit.to_string(),
+        si_value: Some(value * c.scale + c.offset),
+        si_unit: Some(c.si_unit.to_string()),
+        currency: None,
+      },
+      None => Self {
+        dimension: Dimensions::Quantity,
+        value,
+        unit: unit.to_string(),
+        si_value: None,
+        si_unit: None,
+        currency: None,
+      },
+    }
+  }
+
+  pub fn money(value: f64, unit: &str) -> Self {
+    let (currency, scale) = match currency(unit) {
+      Some((code, scale)) => (Some(code), scale),
+      None => (None, 1.),
+    };
+
+    Self {
+      dimension: Dimensions::Money,
+      value,
+      unit: unit.to_string(),
+      si_value: Some(value * scale),
+      si_unit: None,
+      currency,
+    }
+  }
+
+  // Units of mass, volume, distance and temperature, e.g. "cup" or "kg".
+  pub fn is_unit(word: &str) -> bool {
+    conversion(word).is_some()
+  }
+}
+
+fn conversion(unit: &str) -> Option<Conversion> {
+  let unit = unit
+    .trim_end_matches('.')
+    .to_lowercase();
+
+  if let Some(c) = singular_conversion(&unit) {
+    return Some(c);
+  }
+
+  // plurals, e.g. "cups", "inches"
+  unit
+    .strip_suffix("es")
+    .and_then(singular_conversion)
+    .or_else(|| {
+      unit
+        .strip_suffix('s')
+        .and_then(singular_conversion)
+    })
+}
+
+fn singular_conversion(unit: &str) -> Option<Conversion> {
+  let (dimension, si_unit, scale, offset) = match unit {
+    // mass
+    "mg" | "milligram" | "milligramme" => (Dimensions::Mass, "kg", 0.000001, 0.),
+    "g" | "gram" | "gramme" => (Dimensions::Mass, "kg", 0.001, 0.),
+    "kg" | "kilo" | "kilogram" | "kilogramme" => (Dimensions::Mass, "kg", 1., 0.),
+    "tonne" => (Dimensions::Mass, "kg", 1000., 0.),
+    "oz" | "ounce" => (Dimensions::Mass, "kg", 0.028349523125, 0.),
+    "lb" | "pound" => (Dimensions::Mass, "kg", 0.45359237, 0.),
+    "stone" => (Dimensions::Mass, "kg", 6.35029318, 0.),
+    // volume
+    "ml" | "millilitre" | "milliliter" => (Dimensions::Volume, "m3", 0.000001, 0.),
+    "cl" | "centilitre" | "centiliter" => (Dimensions::Volume, "m3", 0.00001, 0.),
+    "l" | "litre" | "liter" => (Dimensions::Volume, "m3", 0.001, 0.),
+    "tsp" | "teaspoon" => (Dimensions::Volume, "m3", 0.00000492892159375, 0.),
+    "tbsp" | "tablespoon" => (Dimensions::Volume, "m3", 0.00001478676478125, 0.),
+    "cup" => (Dimensions::Volume, "m3", 0.0002365882365, 0.),
+    "pint" => (Dimensions::Volume, "m3", 0.000473176473, 0.),
+    "quart" => (Dimensions::Volume, "m3", 0.000946352946, 0.),
+    "gal" | "gallon" => (Dimensions::Volume, "m3", 0.003785411784, 0.),
+    // distance
+    "mm" | "millimetre" | "millimeter" => (Dimensions::Distance, "m", 0.001, 0.),
+    "cm" | "centimetre" | "centimeter" => (Dimensions::Distance, "m", 0.01, 0.),
+    "m" | "metre" | "meter" => (Dimensions::Distance, "m", 1., 0.),
+    "km" | "kilometre" | "kilometer" => (Dimensions::Distance, "m", 1000., 0.),
+    "in" | "inch" => (Dimensions::Distance, "m", 0.0254, 0.),
+    "ft" | "foot" | "feet" => (Dimensions::Distance, "m", 0.3048, 0.),
+    "yd" | "yard" => (Dimensions::Distance, "m", 0.9144, 0.),
+    "mi" | "mile" => (Dimensions::Distance, "m", 1609.344, 0.),
+    // temperature
+    "k" | "kelvin" => (Dimensions::Temperature, "K", 1., 0.),
+    "c" | "°c" | "celsius" => (Dimensions::Temperature, "K", 1., 273.15),
+    "f" | "°f" | "fahrenheit" => (Dimensions::Temperature, "K", 5. / 9., 273.15 - 160. / 9.),
+    _ => return None,
+  };
+
+  Some(Conversion {
+    dimension,
+    si_unit,
+    scale,
+    offset,
+  })
+}
+
+// The currency code of a money unit, and the scale to its main unit, e.g. ("USD", 0.01) for cents.
+fn currency(unit: &str) -> Option<(String, f64)> {
+  let (code, scale) = match unit
+    .to_lowercase()
+    .as_str()
+  {
+    "$" | "dollar" | "dollars" => ("USD", 1.),
+    "cent" | "cents" | "¢" => ("USD", 0.01),
+    "€" | "euro" | "euros" => ("EUR", 1.),
+    "£" | "pound" | "pounds" | "quid" => ("GBP", 1.),
+    "p" | "pence" | "penny" => ("GBP", 0.01),
+    "¥" | "yen" => ("JPY", 1.),
+    "₹" | "rupee" | "rupees" => ("INR", 1.),
+    // duckling already gives most currencies as codes, e.g. "EUR"
+    u if u.len() == 3 && u.chars().all(|c| c.is_ascii_alphabetic()) => {
+      return Some((u.to_uppercase(), 1.))
+    }
+    _ => return None,
+  };
+
+  Some((code.to_string(), scale))
+}
@@ -4,9 +4,12 @@ pub mod symbol;
 pub mod sema_sentence;
 pub mod agents;
 pub mod action;
+pub mod contact;
+pub mod diagnostic;
 pub mod entity;
 pub mod event;
 pub mod location;
+pub mod measurement;
 pub mod relation;
 pub mod temporal;
 
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "query_type")]
 pub enum Queries {
@@ -6,20 +8,20 @@ pub enum Queries {
   Query(Query)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Query {
   pub symbol: String,
   pub properties: Vec<QueryProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum QueryProperties {}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Subject {
   pub symbol: String,
   pub properties: Vec<SubjectProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum SubjectProperties {}
\ No newline at end of file
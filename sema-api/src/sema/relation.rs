@@ -1,12 +1,17 @@
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use schemars::JsonSchema;
+
+use super::symbol::Symbol;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "relation_type")]
 pub enum Relations {
   Ownership(Ownership),
   Origin(Origin),
+  Kinship(Kinship),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Ownership {
   pub symbol: String,
   pub properties: Vec<OwnershipProperties>,
@@ -21,7 +26,7 @@ impl Ownership {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "ownership_property_type")]
 pub enum OwnershipProperties {
@@ -29,7 +34,7 @@ pub enum OwnershipProperties {
   Owned { owned: String },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Origin {
   pub symbol: String,
   pub properties: Vec<OriginProperties>,
@@ -44,7 +49,7 @@ impl Origin {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "origin_property_type")]
 pub enum OriginProperties {
@@ -53,4 +58,39 @@ pub enum OriginProperties {
   Agent { agent: String },
   Location { location: String },
   Event { event: String },
-}
\ No newline at end of file
+}
+
+// "Anna is the sister of $ego": the person, their relative, and how they're related.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Kinship {
+  pub symbol: String,
+  pub properties: Vec<KinshipProperties>,
+}
+
+impl Kinship {
+  pub fn new(symbol: &mut Symbol, person: &str, kinship: &str, relative: &str) -> Self {
+    Self {
+      symbol: symbol.next_symbol(),
+      properties: vec![
+        KinshipProperties::Person {
+          person: person.to_string(),
+        },
+        KinshipProperties::Kinship {
+          kinship: kinship.to_string(),
+        },
+        KinshipProperties::Relative {
+          relative: relative.to_string(),
+        },
+      ],
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "kinship_property_type")]
+pub enum KinshipProperties {
+  Person { person: String },
+  Kinship { kinship: String },
+  Relative { relative: String },
+}
@@ -1,10 +1,14 @@
+use schemars::JsonSchema;
+
+use crate::sentence::SentenceTypes;
+
 use super::{
-  action::Action, agents::Agents, entity::Entity, event::Event, location::Locations,
-  query::Queries, relation::Relations, temporal::Temporals,
+  action::Action, agents::Agents, contact::Contact, diagnostic::Diagnostic, entity::Entity,
+  event::Event, location::Locations, query::Queries, relation::Relations, temporal::Temporals,
 };
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SemaSentence {
   pub agents: Vec<Agents>,
 
@@ -21,6 +25,16 @@ pub struct SemaSentence {
   pub events: Vec<Event>,
 
   pub queries: Vec<Queries>,
+
+  // Contact details that couldn't be attached to an agent.
+  #[serde(default)]
+  pub contacts: Vec<Contact>,
+
+  #[serde(default)]
+  pub mood: Option<SentenceTypes>,
+
+  #[serde(default)]
+  pub diagnostics: Vec<Diagnostic>,
 }
 
 impl SemaSentence {
@@ -34,6 +48,9 @@ impl SemaSentence {
       actions: Vec::new(),
       events: Vec::new(),
       queries: Vec::new(),
+      contacts: Vec::new(),
+      mood: None,
+      diagnostics: Vec::new(),
     }
   }
 
@@ -45,4 +62,40 @@ impl SemaSentence {
     }
     false
   }
+
+  pub fn has_addressee_agent(&self) -> bool {
+    self
+      .agents
+      .iter()
+      .any(|agent| agent.is_addressee())
+  }
+
+  // The highest numbered symbol in use, e.g. 4 for "$4", so new objects don't reuse one.
+  pub fn max_symbol(&self) -> i32 {
+    serde_json::to_value(self)
+      .map(|value| {
+        let mut max = 0;
+
+        for items in value
+          .as_object()
+          .into_iter()
+          .flat_map(|collections| collections.values())
+          .filter_map(|items| items.as_array())
+        {
+          for item in items {
+            if let Some(n) = item
+              .get("symbol")
+              .and_then(|s| s.as_str())
+              .and_then(|s| s.strip_prefix('$'))
+              .and_then(|s| s.parse::<i32>().ok())
+            {
+              max = max.max(n);
+            }
+          }
+        }
+
+        max
+      })
+      .unwrap_or(0)
+  }
 }
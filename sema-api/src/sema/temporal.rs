@@ -1,4 +1,5 @@
 use strum_macros::{self, Display};
+use schemars::JsonSchema;
 
 /*
 examples:
@@ -18,7 +19,7 @@ Questions:
   -
 */
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "temporal_type")]
 pub enum Temporals {
@@ -43,13 +44,13 @@ impl Temporals {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Tense {
   pub symbol: String,
   pub tense: Tenses,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Tenses {
   Past,
@@ -57,13 +58,13 @@ pub enum Tenses {
   Future,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Relative {
   pub symbol: String,
   pub properties: Vec<RelativeProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "relative_type")]
@@ -76,14 +77,14 @@ pub enum RelativeProperties {
   Next { next: String },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Absolute {
   pub symbol: String,
   pub text: String,
   pub properties: Vec<AbsoluteProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "absolute_property_type")]
@@ -100,7 +101,7 @@ pub enum AbsoluteProperties {
   ISO { iso: String }, // ISO 8601
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DaysOfWeek {
   Monday,
@@ -125,6 +126,19 @@ impl DaysOfWeek {
     }
   }
 
+  // The day's code in an iCalendar RRULE, e.g. "MO" in "BYDAY=MO".
+  pub fn rrule_code(&self) -> &'static str {
+    match self {
+      DaysOfWeek::Monday => "MO",
+      DaysOfWeek::Tuesday => "TU",
+      DaysOfWeek::Wednesday => "WE",
+      DaysOfWeek::Thursday => "TH",
+      DaysOfWeek::Friday => "FR",
+      DaysOfWeek::Saturday => "SA",
+      DaysOfWeek::Sunday => "SU",
+    }
+  }
+
   pub fn from_str(s: &str) -> Option<Self> {
     match s.to_lowercase().as_ref() {
       "monday" => Some(DaysOfWeek::Monday),
@@ -139,13 +153,13 @@ impl DaysOfWeek {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct General {
   pub symbol: String,
   pub general: GeneralTemporal,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum GeneralTemporal {
   Past,
@@ -153,13 +167,13 @@ pub enum GeneralTemporal {
   Future,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Duration {
   pub symbol: String,
   pub properties: Vec<DurationProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 // #[serde(tag = "temporal_duration_type")]
@@ -176,13 +190,15 @@ pub enum DurationProperties {
   End { end: String },     // to another temporal?
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Something that repeats, e.g. "every other Friday until June".
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Interval {
   pub symbol: String,
+  pub text: String,
   pub properties: Vec<IntervalProperties>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Display)]
+#[derive(Debug, Clone, Serialize, Deserialize, Display, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 // #[serde(tag = "temporal_interval_type")]
 #[serde(untagged)]
@@ -194,4 +210,12 @@ pub enum IntervalProperties {
   Hours { hours: i32 },
   Minutes { minutes: i32 },
   Seconds { seconds: i32 },
+  Times { times: i32 }, // how many times in each interval, "twice a week"
+  DayOfWeek { day_of_week: DaysOfWeek },
+  Month { month: i32 }, // 1 is January
+  Hour { hour: i32 },
+  Minute { minute: i32 },
+  Until { until: String }, // symbol to the temporal it ends at
+  Frequency { frequency: String }, // an adverb without a fixed rate, "usually"
+  RRule { rrule: String }, // iCalendar (RFC 5545), "FREQ=WEEKLY;INTERVAL=2;BYDAY=FR"
 }
@@ -4,11 +4,14 @@ use link_parser_rust_bindings::lp::{
   sentence::Sentence as LPSentence, 
   word::Word
 };
+use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 
 use super::{actions::Actions, Entity};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// The mood of a sentence. Commands, questions and statements are routed differently.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum SentenceTypes {
   Imperative,
   Interrogative,
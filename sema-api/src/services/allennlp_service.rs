@@ -1,8 +1,10 @@
 use anyhow::Result;
+use schemars::JsonSchema;
+use crate::config::server_config;
 use strum_macros::{EnumString};
 use std::{str::FromStr};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SRLResponse {
   pub verbs: Vec<SRLVerb>,
 
@@ -22,14 +24,14 @@ impl SRLResponse {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SRLVerb {
   pub description: String,
   pub verb: String,
   pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub enum SRLArgs {
   V,
   ARG0,
@@ -42,7 +44,7 @@ pub enum SRLArgs {
   Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, EnumString)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, EnumString, JsonSchema)]
 pub enum SRLArgMModifiers {
   COM, // comitative
   LOC, // locative
@@ -66,7 +68,7 @@ pub enum SRLArgMModifiers {
   Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SRLFrames {
   pub verb: String,
 
@@ -100,7 +102,10 @@ impl SRLFrames {
             "ARG3" => SRLArgs::ARG3,
             "ARG4" => SRLArgs::ARG4,
             "ARGM" => {
-              let argm_mod = SRLArgMModifiers::from_str(tag_parts[2]).unwrap();
+              let argm_mod = tag_parts
+                .get(2)
+                .and_then(|m| SRLArgMModifiers::from_str(m).ok())
+                .unwrap_or(SRLArgMModifiers::Unknown);
 
               SRLArgs::ARGM(argm_mod)
             },
@@ -108,11 +113,12 @@ impl SRLFrames {
             _ => SRLArgs::Unknown,
           };
 
-          current_frame = Some(SRLFrame { arg, words: vec![words[idx].clone()] })
+          current_frame = Some(SRLFrame { arg, words: vec![words[idx].clone()], token_indices: vec![idx] })
         },
         "I" => {
           if let Some(cf) = current_frame.as_mut() {
             cf.words.push(words[idx].clone());
+            cf.token_indices.push(idx);
           }
         },
         "O" => {},
@@ -137,10 +143,12 @@ impl SRLFrames {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SRLFrame {
   pub arg: SRLArgs,
   pub words: Vec<String>,
+  #[serde(default)]
+  pub token_indices: Vec<usize>, // indices into SRLResponse.words
 }
 
 impl SRLFrame {
@@ -155,11 +163,15 @@ pub struct SRLBody<'a> {
 }
 
 pub async fn get_semantic_role_labels(sentence: &str) -> Result<SRLResponse> {
+  get_semantic_role_labels_from(&server_config().srl_url, sentence).await
+}
+
+pub async fn get_semantic_role_labels_from(srl_url: &str, sentence: &str) -> Result<SRLResponse> {
   let client = reqwest::Client::new();
   let body = SRLBody { sentence };
 
   let resp = client
-    .post("https://srl.allennlp.sematic.rocks/predict")
+    .post(srl_url)
     .json::<SRLBody>(&body)
     .send()
     // .map_err(|e| e.into())
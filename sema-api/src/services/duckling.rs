@@ -20,15 +20,32 @@ pub struct DucklingParseResponse {
 #[serde(tag = "type")]
 #[serde(rename_all = "lowercase")]
 pub enum DucklingValueOption {
-  Value { grain: String, value: String, values: Option<Vec<DucklingValueOption>> },
+  Value {
+    // Only times have a grain.
+    #[serde(default)]
+    grain: String,
+    // A string for times, and a number for numbers, quantities and money.
+    value: serde_json::Value,
+    // e.g. "cup", "kilometre", "USD"
+    #[serde(default)]
+    unit: Option<String>,
+    // What a quantity is of, e.g. "flour" in "two cups of flour"
+    #[serde(default)]
+    product: Option<String>,
+    values: Option<Vec<DucklingValueOption>>,
+  },
   Interval { to: NoTypeValue, from: NoTypeValue, values: Option<Vec<DucklingValueOption>> },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NoTypeValue {
-  pub value: String,
+  pub value: serde_json::Value,
 
+  #[serde(default)]
   pub grain: String,
+
+  #[serde(default)]
+  pub unit: Option<String>,
 }
 
 pub async fn duckling_parse_sentence(sentence: &str) -> Result<Vec<DucklingParseResponse>> {
@@ -0,0 +1,7 @@
+pub mod verbnet_classes;
+
+use self::verbnet_classes::VerbNetClasses;
+
+pub fn init_verbnet_cells(data_path: &str) {
+  VerbNetClasses::init(data_path);
+}
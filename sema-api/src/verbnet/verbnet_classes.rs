@@ -0,0 +1,279 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+
+use once_cell::sync::OnceCell;
+
+use crate::sema::action::ThematicRoles;
+
+// A VerbNet class as stored in verbnet.json, e.g. "give-13.1".
+// Subclasses inherit the roles of their parent class.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerbNetClassData {
+  pub id: String,
+
+  pub members: Vec<String>,
+
+  pub roles: Vec<String>,
+
+  // The role of the verb's direct object, when it isn't the one the roles would give, e.g.
+  // "Recipient" for "tell John".
+  #[serde(default)]
+  pub object: Option<String>,
+
+  #[serde(default)]
+  pub subclasses: Vec<VerbNetClassData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerbNetData {
+  pub classes: Vec<VerbNetClassData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerbNetClass {
+  pub id: String,
+
+  pub roles: Vec<ThematicRoles>,
+
+  pub object: Option<ThematicRoles>,
+}
+
+// Where an argument sits relative to the verb, as found in the linkage.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentSlots {
+  Subject,
+  Object,
+  IndirectObject,       // first object of a two object verb, "I gave HIM the book"
+  Prepositional(String), // object of a preposition, the preposition is kept, e.g. "for"
+}
+
+impl VerbNetClass {
+  pub fn has_role(&self, role: &ThematicRoles) -> bool {
+    self
+      .roles
+      .contains(role)
+  }
+
+  // Picks the class's role for an argument slot.
+  // Roles are tried in order, so the first role the class has wins.
+  pub fn role_for_slot(&self, slot: &ArgumentSlots) -> Option<ThematicRoles> {
+    if let (ArgumentSlots::Object, Some(object)) = (slot, &self.object) {
+      return Some(object.clone());
+    }
+
+    let candidates = match slot {
+      ArgumentSlots::Subject => vec![
+        ThematicRoles::Agent,
+        ThematicRoles::Experiencer,
+        ThematicRoles::Cause,
+        ThematicRoles::Pivot,
+        ThematicRoles::Theme,
+        ThematicRoles::Patient,
+      ],
+      ArgumentSlots::Object => vec![
+        ThematicRoles::Patient,
+        ThematicRoles::Theme,
+        ThematicRoles::Product,
+        ThematicRoles::Result,
+        ThematicRoles::Stimulus,
+        ThematicRoles::Topic,
+        ThematicRoles::CoAgent,
+        ThematicRoles::Beneficiary,
+        ThematicRoles::Recipient,
+      ],
+      ArgumentSlots::IndirectObject => vec![
+        ThematicRoles::Recipient,
+        ThematicRoles::Beneficiary,
+        ThematicRoles::Destination,
+        ThematicRoles::Goal,
+      ],
+      ArgumentSlots::Prepositional(preposition) => match preposition.as_str() {
+        "to" | "toward" | "towards" => vec![
+          ThematicRoles::Recipient,
+          ThematicRoles::Destination,
+          ThematicRoles::Goal,
+          ThematicRoles::Beneficiary,
+        ],
+        "for" => vec![
+          ThematicRoles::Beneficiary,
+          ThematicRoles::Goal,
+          ThematicRoles::Asset,
+        ],
+        "from" | "off" | "out" => vec![ThematicRoles::Source],
+        "with" => vec![
+          ThematicRoles::Instrument,
+          ThematicRoles::CoAgent,
+          ThematicRoles::CoTheme,
+          ThematicRoles::Material,
+        ],
+        "into" | "onto" | "in" | "on" | "at" => vec![
+          ThematicRoles::Destination,
+          ThematicRoles::Location,
+          ThematicRoles::Goal,
+        ],
+        "about" | "of" => vec![ThematicRoles::Topic, ThematicRoles::Theme],
+        "by" => vec![ThematicRoles::Instrument],
+        _ => vec![],
+      },
+    };
+
+    let subject_role = match slot {
+      ArgumentSlots::Subject => None,
+      _ => self.role_for_slot(&ArgumentSlots::Subject),
+    };
+
+    candidates
+      .into_iter()
+      .filter(|role| Some(role) != subject_role.as_ref())
+      .find(|role| self.has_role(role))
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerbNetClasses {
+  classes: HashMap<String, VerbNetClass>,
+
+  // verb lemma -> class ids, in the order they appear in the data file
+  members: HashMap<String, Vec<String>>,
+}
+
+impl VerbNetClasses {
+  pub fn new(data_path: &str) -> Result<Self> {
+    let path = format!("{}/verbnet.json", data_path);
+    let file = File::open(&path)?;
+    let reader = BufReader::new(file);
+    let data: VerbNetData = serde_json::from_reader(reader)?;
+
+    let mut verbnet_classes = VerbNetClasses {
+      classes: HashMap::new(),
+      members: HashMap::new(),
+    };
+
+    for class in data.classes {
+      verbnet_classes.add_class(class, &[], None);
+    }
+
+    Ok(verbnet_classes)
+  }
+
+  fn add_class(
+    &mut self,
+    class: VerbNetClassData,
+    parent_roles: &[ThematicRoles],
+    parent_object: Option<&ThematicRoles>,
+  ) {
+    let mut roles = parent_roles.to_vec();
+
+    let object = class
+      .object
+      .as_deref()
+      .map(ThematicRoles::from_str)
+      .or_else(|| parent_object.cloned());
+
+    for role in class
+      .roles
+      .iter()
+    {
+      let role = ThematicRoles::from_str(role);
+      if !roles.contains(&role) {
+        roles.push(role);
+      }
+    }
+
+    for member in class
+      .members
+      .iter()
+    {
+      let class_ids = self
+        .members
+        .entry(member.to_lowercase())
+        .or_insert(vec![]);
+
+      if !class_ids.contains(&class.id) {
+        class_ids.push(class.id.clone());
+      }
+    }
+
+    for subclass in class.subclasses {
+      self.add_class(subclass, &roles, object.as_ref());
+    }
+
+    self
+      .classes
+      .insert(
+        class.id.clone(),
+        VerbNetClass {
+          id: class.id,
+          roles,
+          object,
+        },
+      );
+  }
+
+  pub fn init(data_path: &str) {
+    let verbnet_classes =
+      VerbNetClasses::new(data_path).expect("Unable to create VerbNetClasses instance");
+    VERBNET_CLASSES
+      .set(verbnet_classes)
+      .expect("Unable to set VERBNET_CLASSES");
+  }
+
+  pub fn get(class_id: &str) -> Option<&'static VerbNetClass> {
+    let verbnet_classes = VERBNET_CLASSES
+      .get()
+      .expect("VERBNET_CLASSES is not initialized");
+
+    verbnet_classes
+      .classes
+      .get(class_id)
+  }
+
+  pub fn get_by_member(lemma: &str) -> Vec<&'static VerbNetClass> {
+    let verbnet_classes = VERBNET_CLASSES
+      .get()
+      .expect("VERBNET_CLASSES is not initialized");
+
+    verbnet_classes
+      .members
+      .get(&lemma.to_lowercase())
+      .map(|class_ids| {
+        class_ids
+          .iter()
+          .filter_map(|class_id| {
+            verbnet_classes
+              .classes
+              .get(class_id)
+          })
+          .collect()
+      })
+      .unwrap_or_default()
+  }
+
+  // Verbs are often members of several classes, e.g. "bake" is in both build-26.1 and cooking-45.3.
+  // Without word senses the best we can do is prefer a class that has a role for the verb's object,
+  // if it has one, and otherwise take the first class listed.
+  pub fn classify(lemma: &str, has_object: bool) -> Option<&'static VerbNetClass> {
+    let classes = VerbNetClasses::get_by_member(lemma);
+
+    if has_object {
+      if let Some(class) = classes
+        .iter()
+        .find(|class| {
+          class
+            .role_for_slot(&ArgumentSlots::Object)
+            .is_some()
+        })
+      {
+        return Some(class);
+      }
+    }
+
+    classes
+      .first()
+      .copied()
+  }
+}
+
+pub static VERBNET_CLASSES: OnceCell<VerbNetClasses> = OnceCell::new();
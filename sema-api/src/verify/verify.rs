@@ -1,216 +1,500 @@
-// use anyhow::Result;
-// use link_parser_rust_bindings::lp::{disjunct::ConnectorPointing, link_types::LinkTypes};
-
-// use crate::{
-//   nlp::sentence_parts::SentenceParts,
-//   sema::{
-//     action::Action,
-//     agents::{Agents, Ego},
-//     entity::{Entity, EntityProperties},
-//     sema_sentence::SemaSentence,
-//     symbol::Symbol,
-//   },
-// };
-
-// pub async fn verify_and_repair_sema_json(
-//   sema_sentence: SemaSentence,
-//   part: SentenceParts,
-// ) -> Result<SemaSentence> {
-//   let mut symbol = Symbol::new(0);
-//   let mut repaired_sentence = sema_sentence.clone();
-
-//   if verify_actions(&sema_sentence, &part)? {
-//     // return Ok(());
-//     println!("actions verified");
-//   } else {
-//     println!("actions not verified");
-//     repaired_sentence = repair_actions(&sema_sentence, &part, &mut symbol)?;
-//   }
-
-//   let repaired_sentence = repair_agents(&repaired_sentence, &part, &mut symbol)?;
-
-//   let repaired_sentence = repair_entities(&repaired_sentence, &part, &mut symbol)?;
-
-//   Ok(repaired_sentence)
-// }
-
-// pub fn repair_agents(
-//   sema_sentence: &SemaSentence,
-//   part: &SentenceParts,
-//   symbol: &mut Symbol,
-// ) -> Result<SemaSentence> {
-//   let mut repaired_sentence = sema_sentence.clone();
-
-//   // "I" and "My" detection
-//   let ego_exists_in_sentence = part
-//     .links
-//     .words
-//     .iter()
-//     .any(|lp_word| {
-//       lp_word
-//         .word
-//         .to_lowercase()
-//         == "i"
-//         || lp_word
-//           .word
-//           .to_lowercase()
-//           == "my"
-//     });
-
-//   if ego_exists_in_sentence && !sema_sentence.has_ego_agent() {
-//     let agent = Agents::Ego(Ego::new(symbol));
-
-//     repaired_sentence
-//       .agents
-//       .push(agent);
-//   }
-
-//   Ok(repaired_sentence)
-// }
-
-// pub fn verify_actions(sema_sentence: &SemaSentence, part: &SentenceParts) -> Result<bool> {
-//   let verbs = part
-//     .links
-//     .get_verbs();
-//   // dbg!(&verbs);
-
-//   if sema_sentence
-//     .actions
-//     .len()
-//     != verbs.len()
-//   {
-//     return Ok(false);
-//   }
-
-//   for verb in verbs.iter() {
-//     let action = sema_sentence
-//       .actions
-//       .iter()
-//       .find(|action| action.action_type == verb.word);
-
-//     if action.is_none() {
-//       return Ok(false);
-//     }
-//   }
-
-//   Ok(true)
-// }
-
-// pub fn repair_actions(
-//   sema_sentence: &SemaSentence,
-//   part: &SentenceParts,
-//   symbol: &mut Symbol,
-// ) -> Result<SemaSentence> {
-//   let mut repaired_sentence = sema_sentence.clone();
-
-//   let verbs = part
-//     .links
-//     .get_verbs();
-//   // dbg!(&verbs);
-
-//   let actions = verbs
-//     .into_iter()
-//     .map(|verb| Action::new(verb.word.clone(), symbol))
-//     .collect::<Vec<Action>>();
-
-//   repaired_sentence
-//     .actions
-//     .clear();
-
-//   repaired_sentence.actions = actions;
-
-//   Ok(repaired_sentence)
-// }
-
-// pub fn repair_entities(
-//   sema_sentence: &SemaSentence,
-//   part: &SentenceParts,
-//   symbol: &mut Symbol,
-// ) -> Result<SemaSentence> {
-//   let mut repaired_sentence = sema_sentence.clone();
-
-//   repaired_sentence
-//     .entities
-//     .clear();
-
-//   let known_nouns = part
-//     .links
-//     .get_known_nouns();
-
-//   // dbg!(&known_nouns);
-
-//   for noun in known_nouns.iter() {
-//     let mut entity = Entity::new(noun.word.clone(), symbol);
-
-//     let connected_words = part
-//       .links
-//       .get_connected_words(noun)?;
-//     // dbg!(&connected_words);
-
-//     for cw in connected_words.iter() {
-//       if noun.has_disjunct(LinkTypes::A, ConnectorPointing::Left)
-//         && cw.has_disjunct(LinkTypes::A, ConnectorPointing::Right)
-//       {
-//         let mod_type = cw.word.clone();
-//         let mut amps = vec![];
-
-//         if cw.has_disjunct(LinkTypes::EA, ConnectorPointing::Left) {
-//           let amp_words = part
-//             .links
-//             .get_connected_words(cw)?;
-//           dbg!(&amp_words);
-
-//           for amp_word in amp_words.iter() {
-//             if amp_word.has_disjunct(LinkTypes::EA, ConnectorPointing::Right) {
-//               amps.push(
-//                 amp_word
-//                   .word
-//                   .clone(),
-//               );
-//             }
-//           }
-//         };
-
-//         entity
-//           .properties
-//           .push(EntityProperties::Modifier {
-//             modifier_type: mod_type,
-//             modifier: None,
-//             amplifiers: amps,
-//           });
-//       }
-//     }
-
-//     repaired_sentence
-//       .entities
-//       .push(entity);
-//   }
-
-//   // let entities = part
-//   //   .links
-//   //   .get_entities();
-//   // dbg!(&entities);
-
-//   // let entities = entities
-//   //   .into_iter()
-//   //   .map(|entity| {
-//   //     let mut agent = Person::new(symbol);
-
-//   //     agent
-//   //       .properties
-//   //       .push(PersonProperties::Name {
-//   //         name: entity.word.clone(),
-//   //       });
-
-//   //     Agents::Person(agent)
-//   //   })
-//   //   .collect::<Vec<Agents>>();
-
-//   // repaired_sentence
-//   //   .agents
-//   //   .clear();
-
-//   // repaired_sentence.agents = entities;
-
-//   Ok(repaired_sentence)
-// }
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+
+use crate::{
+  nlp::sentence_parts::SentenceParts,
+  parse::link_parse::{
+    parse_actions::{action_from_verb, action_verbs},
+    parse_agents::{find_companies, find_names, name_properties, EGO_WORDS},
+  },
+  sema::{
+    agents::{Agents, Ego, Genders, Person, PersonProperties},
+    diagnostic::{Diagnostic, DiagnosticKinds, DiagnosticStages},
+    location::Locations,
+    query::Queries,
+    relation::Relations,
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+    temporal::Temporals,
+  },
+};
+
+// Pronouns that refer to people other than the speaker. There's no agent type for them yet,
+// so they're reported when there's no agent in the sentence they could be about.
+pub static OTHER_PERSON_PRONOUNS: [&str; 9] =
+  ["you", "he", "him", "she", "her", "we", "us", "they", "them"];
+
+// Checks a SemaSentence from any backend against the sentence parts it was made from.
+// Anything that can be repaired is, and everything found is added to the sentence's diagnostics.
+pub fn verify_and_repair_sema_json(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+) -> Result<SemaSentence> {
+  let mut symbol = Symbol::new(sema_sentence.max_symbol());
+
+  let repaired_sentence = repair_actions(sema_sentence, part, &mut symbol)?;
+
+  let repaired_sentence = repair_agents(&repaired_sentence, part, &mut symbol)?;
+
+  let repaired_sentence = repair_symbols(&repaired_sentence);
+
+  Ok(repaired_sentence)
+}
+
+// Every verb should have an action.
+pub fn repair_actions(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+) -> Result<SemaSentence> {
+  let mut repaired_sentence = sema_sentence.clone();
+
+  // Actions are matched to verbs by lemma, since other backends don't keep word positions.
+  let mut unmatched_actions = sema_sentence
+    .actions
+    .iter()
+    .map(|action| {
+      action
+        .action_type
+        .as_str()
+    })
+    .collect::<Vec<&str>>();
+
+  for v in action_verbs(part).iter() {
+    let lemma = part.get_word_lemma(v);
+
+    if let Some(i) = unmatched_actions
+      .iter()
+      .position(|action_type| *action_type == lemma)
+    {
+      unmatched_actions.remove(i);
+      continue;
+    }
+
+    let action = action_from_verb(part, v, symbol);
+
+    repaired_sentence
+      .diagnostics
+      .push(Diagnostic::new(
+        DiagnosticStages::Verify,
+        DiagnosticKinds::Repaired,
+        format!(
+          "added action {} for the verb \"{}\", it has no arguments",
+          action.symbol,
+          v.get_cleaned_word()
+        ),
+        vec![action
+          .symbol
+          .clone()],
+      ));
+
+    repaired_sentence
+      .actions
+      .push(action);
+  }
+
+  Ok(repaired_sentence)
+}
+
+// Every name and person pronoun should be covered by an agent.
+pub fn repair_agents(
+  sema_sentence: &SemaSentence,
+  part: &SentenceParts,
+  symbol: &mut Symbol,
+) -> Result<SemaSentence> {
+  let mut repaired_sentence = sema_sentence.clone();
+
+  // "I", "my" and "me"
+  let ego_exists_in_sentence = part
+    .links
+    .words
+    .iter()
+    .any(|lp_word| {
+      let w = lp_word
+        .get_cleaned_word()
+        .to_lowercase();
+      EGO_WORDS.contains(&w.as_str())
+    });
+
+  if ego_exists_in_sentence && !sema_sentence.has_ego_agent() {
+    let ego = Ego::new(symbol);
+
+    repaired_sentence
+      .diagnostics
+      .push(Diagnostic::new(
+        DiagnosticStages::Verify,
+        DiagnosticKinds::Repaired,
+        format!("added ego agent {}", ego.symbol),
+        vec![ego.symbol.clone()],
+      ));
+
+    repaired_sentence
+      .agents
+      .push(Agents::Ego(ego));
+  }
+
+  for name_vec in find_names(part, &find_companies(part)).iter() {
+    let name_props = name_properties(name_vec);
+
+    let covered = sema_sentence
+      .agents
+      .iter()
+      .any(|agent| match agent {
+        Agents::Person(person) => name_props
+          .iter()
+          .all(|np| {
+            person
+              .properties
+              .iter()
+              .any(|pp| PersonProperties::value(pp) == PersonProperties::value(np))
+          }),
+        _ => false,
+      });
+
+    if covered {
+      continue;
+    }
+
+    let mut person = Person::new(symbol);
+
+    person
+      .properties
+      .extend(name_props);
+
+    repaired_sentence
+      .diagnostics
+      .push(Diagnostic::new(
+        DiagnosticStages::Verify,
+        DiagnosticKinds::Repaired,
+        format!(
+          "added person {} for the name \"{}\"",
+          person.symbol,
+          name_vec
+            .iter()
+            .map(|w| w.get_cleaned_word())
+            .collect::<Vec<String>>()
+            .join(" ")
+        ),
+        vec![person
+          .symbol
+          .clone()],
+      ));
+
+    repaired_sentence
+      .agents
+      .push(Agents::Person(person));
+  }
+
+  for lp_word in part
+    .links
+    .words
+    .iter()
+  {
+    let w = lp_word
+      .get_cleaned_word()
+      .to_lowercase();
+
+    if OTHER_PERSON_PRONOUNS.contains(&w.as_str()) && !pronoun_resolves(&w, &repaired_sentence) {
+      repaired_sentence
+        .diagnostics
+        .push(Diagnostic::new(
+          DiagnosticStages::Verify,
+          DiagnosticKinds::Missing,
+          format!("the pronoun \"{}\" isn't covered by an agent", w),
+          vec![],
+        ));
+    }
+  }
+
+  Ok(repaired_sentence)
+}
+
+// Whether there's an agent the pronoun could be about: the addressee for "you" in a command, a
+// person who isn't known to be the other gender for "he" and "she", and a group, a company or
+// more than one person for "we" and "they".
+fn pronoun_resolves(pronoun: &str, sema_sentence: &SemaSentence) -> bool {
+  let people = sema_sentence
+    .agents
+    .iter()
+    .filter_map(|agent| match agent {
+      Agents::Person(person) => Some(person),
+      _ => None,
+    })
+    .collect::<Vec<&Person>>();
+
+  let could_be = |person: &&Person, gender: Genders| {
+    person
+      .properties
+      .iter()
+      .all(|p| match p {
+        PersonProperties::Gender { gender: g } => *g == gender || *g == Genders::Unknown,
+        _ => true,
+      })
+  };
+
+  match pronoun {
+    "you" => sema_sentence.has_addressee_agent(),
+    "he" | "him" => people
+      .iter()
+      .any(|p| could_be(p, Genders::Male)),
+    "she" | "her" => people
+      .iter()
+      .any(|p| could_be(p, Genders::Female)),
+    _ => {
+      people.len() > 1
+        || sema_sentence
+          .agents
+          .iter()
+          .any(|agent| matches!(agent, Agents::Group(_) | Agents::Company(_)))
+    }
+  }
+}
+
+// No property should point to a symbol that doesn't exist. Properties that do are removed.
+pub fn repair_symbols(sema_sentence: &SemaSentence) -> SemaSentence {
+  let defined = defined_symbols(sema_sentence);
+
+  let mut repaired_sentence = sema_sentence.clone();
+  let mut diagnostics = vec![];
+
+  let check = |owner: String| SymbolCheck {
+    owner,
+    defined: &defined,
+  };
+
+  for agent in repaired_sentence
+    .agents
+    .iter_mut()
+  {
+    let c = check(agent.get_symbol());
+    match agent {
+      Agents::Ego(ego) => c.retain(&mut ego.properties, &mut diagnostics),
+      Agents::Addressee(addressee) => c.retain(&mut addressee.properties, &mut diagnostics),
+      Agents::Subject(subject) => c.retain(&mut subject.properties, &mut diagnostics),
+      Agents::Person(person) => c.retain(&mut person.properties, &mut diagnostics),
+      Agents::Group(group) => c.retain(&mut group.properties, &mut diagnostics),
+      Agents::Company(company) => c.retain(&mut company.properties, &mut diagnostics),
+    }
+  }
+
+  for entity in repaired_sentence
+    .entities
+    .iter_mut()
+  {
+    check(entity.get_symbol()).retain(&mut entity.properties, &mut diagnostics);
+  }
+
+  for location in repaired_sentence
+    .locations
+    .iter_mut()
+  {
+    let c = check(location.get_symbol());
+    match location {
+      Locations::Relative(relative) => c.retain(&mut relative.properties, &mut diagnostics),
+      Locations::Country(country) => c.retain(&mut country.properties, &mut diagnostics),
+      Locations::City(city) => c.retain(&mut city.properties, &mut diagnostics),
+      Locations::LatLong(lat_long) => c.retain(&mut lat_long.properties, &mut diagnostics),
+      Locations::Direction(_) => (),
+    }
+  }
+
+  for temporal in repaired_sentence
+    .temporal
+    .iter_mut()
+  {
+    let c = check(temporal.get_symbol());
+    match temporal {
+      Temporals::Relative(relative) => c.retain(&mut relative.properties, &mut diagnostics),
+      Temporals::Absolute(absolute) => c.retain(&mut absolute.properties, &mut diagnostics),
+      Temporals::Duration(duration) => c.retain(&mut duration.properties, &mut diagnostics),
+      Temporals::Interval(interval) => c.retain(&mut interval.properties, &mut diagnostics),
+      Temporals::Tense(_) | Temporals::General(_) => (),
+    }
+  }
+
+  for relation in repaired_sentence
+    .relations
+    .iter_mut()
+  {
+    match relation {
+      Relations::Ownership(r) => {
+        check(r.symbol.clone()).retain(&mut r.properties, &mut diagnostics)
+      }
+      Relations::Origin(r) => check(r.symbol.clone()).retain(&mut r.properties, &mut diagnostics),
+      Relations::Kinship(r) => check(r.symbol.clone()).retain(&mut r.properties, &mut diagnostics),
+    }
+  }
+
+  for action in repaired_sentence
+    .actions
+    .iter_mut()
+  {
+    let c = check(action.get_symbol());
+    c.retain(&mut action.properties, &mut diagnostics);
+    c.retain(&mut action.args, &mut diagnostics);
+  }
+
+  for event in repaired_sentence
+    .events
+    .iter_mut()
+  {
+    check(event.symbol.clone()).retain(&mut event.properties, &mut diagnostics);
+  }
+
+  for contact in repaired_sentence
+    .contacts
+    .iter_mut()
+  {
+    check(
+      contact
+        .symbol
+        .clone(),
+    )
+    .retain(&mut contact.properties, &mut diagnostics);
+  }
+
+  repaired_sentence
+    .diagnostics
+    .extend(diagnostics);
+
+  repaired_sentence
+}
+
+// The properties of one object, checked against the symbols defined in the sentence.
+struct SymbolCheck<'a> {
+  owner: String,
+  defined: &'a HashSet<String>,
+}
+
+impl SymbolCheck<'_> {
+  fn retain<T: Serialize>(&self, entries: &mut Vec<T>, diagnostics: &mut Vec<Diagnostic>) {
+    entries.retain(|entry| {
+      // Properties are plain data, but if one can't be read it's kept rather than guessed at.
+      let entry = match serde_json::to_value(entry) {
+        Ok(entry) => entry,
+        Err(_) => return true,
+      };
+
+      let mut referenced = vec![];
+      collect_symbols(&entry, &mut referenced);
+
+      let missing = referenced
+        .into_iter()
+        .filter(|s| {
+          !self
+            .defined
+            .contains(s)
+        })
+        .collect::<Vec<String>>();
+
+      if missing.is_empty() {
+        return true;
+      }
+
+      diagnostics.push(Diagnostic::new(
+        DiagnosticStages::Verify,
+        DiagnosticKinds::Removed,
+        format!(
+          "removed {} from {}, it points to {} which doesn't exist",
+          entry,
+          self.owner,
+          missing.join(", ")
+        ),
+        [vec![self.owner.clone()], missing].concat(),
+      ));
+
+      false
+    });
+  }
+}
+
+fn defined_symbols(sema_sentence: &SemaSentence) -> HashSet<String> {
+  let agents = sema_sentence
+    .agents
+    .iter()
+    .map(Agents::get_symbol);
+  let entities = sema_sentence
+    .entities
+    .iter()
+    .map(|e| e.symbol.clone());
+  let locations = sema_sentence
+    .locations
+    .iter()
+    .map(Locations::get_symbol);
+  let temporal = sema_sentence
+    .temporal
+    .iter()
+    .map(Temporals::get_symbol);
+  let relations = sema_sentence
+    .relations
+    .iter()
+    .map(|r| match r {
+      Relations::Ownership(ownership) => ownership
+        .symbol
+        .clone(),
+      Relations::Origin(origin) => origin
+        .symbol
+        .clone(),
+      Relations::Kinship(kinship) => kinship
+        .symbol
+        .clone(),
+    });
+  let actions = sema_sentence
+    .actions
+    .iter()
+    .map(|a| a.symbol.clone());
+  let events = sema_sentence
+    .events
+    .iter()
+    .map(|e| e.symbol.clone());
+  let queries = sema_sentence
+    .queries
+    .iter()
+    .map(|q| match q {
+      Queries::Subject(subject) => subject
+        .symbol
+        .clone(),
+      Queries::Query(query) => query.symbol.clone(),
+    });
+  let contacts = sema_sentence
+    .contacts
+    .iter()
+    .map(|c| c.symbol.clone());
+
+  agents
+    .chain(entities)
+    .chain(locations)
+    .chain(temporal)
+    .chain(relations)
+    .chain(actions)
+    .chain(events)
+    .chain(queries)
+    .chain(contacts)
+    .collect()
+}
+
+// Collects every string that looks like a numbered symbol, e.g. "$4".
+fn collect_symbols(value: &Value, symbols: &mut Vec<String>) {
+  match value {
+    Value::String(s) => {
+      if symbol_number(s).is_some() {
+        symbols.push(s.clone());
+      }
+    }
+    Value::Array(values) => values
+      .iter()
+      .for_each(|v| collect_symbols(v, symbols)),
+    Value::Object(map) => map
+      .values()
+      .for_each(|v| collect_symbols(v, symbols)),
+    _ => (),
+  }
+}
+
+fn symbol_number(s: &str) -> Option<i32> {
+  s.strip_prefix('$')?
+    .parse()
+    .ok()
+}
@@ -0,0 +1 @@
+pub mod srl_stub;
@@ -0,0 +1,42 @@
+// The predict handler the srl_stub example serves and the srl cross-check test runs against.
+// Sentences that aren't in the fixtures get a response with no verbs.
+use std::collections::HashMap;
+use std::{fs::File, io::BufReader};
+
+use actix_web::{post, web, HttpResponse, Responder};
+use sema_api::services::allennlp_service::SRLResponse;
+use serde_derive::Deserialize;
+
+pub type Fixtures = web::Data<HashMap<String, SRLResponse>>;
+
+#[derive(Debug, Deserialize)]
+struct PredictBody {
+  sentence: String,
+}
+
+// Reads a json file of { "<sentence>": <SRLResponse> }.
+pub fn load_fixtures(path: &str) -> std::io::Result<Fixtures> {
+  let file = File::open(path)?;
+  let fixtures: HashMap<String, SRLResponse> = serde_json::from_reader(BufReader::new(file))?;
+
+  Ok(web::Data::new(fixtures))
+}
+
+#[post("/predict")]
+pub async fn predict(body: web::Json<PredictBody>, fixtures: Fixtures) -> impl Responder {
+  println!("srl stub: {}", body.sentence);
+
+  let srl_resp = fixtures
+    .get(&body.sentence)
+    .cloned()
+    .unwrap_or_else(|| SRLResponse {
+      verbs: vec![],
+      words: body
+        .sentence
+        .split_whitespace()
+        .map(|w| w.to_string())
+        .collect(),
+    });
+
+  HttpResponse::Ok().json(srl_resp)
+}
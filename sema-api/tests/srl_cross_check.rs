@@ -0,0 +1,165 @@
+// Runs the srl client and cross-check against the srl_stub example's stub and canned responses.
+use actix_web::{App, HttpServer};
+use link_parser_rust_bindings::lp::sentence::Sentence as LPSentence;
+use sema_api::{
+  nlp::{duck::Duck, sentence_parts::SentenceParts},
+  parse::link_parse::{link_parse::ParseState, parse_srl::cross_check_srl},
+  sema::{
+    action::{Action, ActionProperties},
+    diagnostic::{DiagnosticKinds, DiagnosticStages},
+    sema_sentence::SemaSentence,
+    symbol::Symbol,
+  },
+  services::allennlp_service::{get_semantic_role_labels_from, SRLResponse},
+};
+
+mod common;
+
+use common::srl_stub::{load_fixtures, predict};
+
+const SENTENCE: &str = "Jeff bought ten ice cream cones for John";
+
+// Starts the stub on a free port and returns its predict url.
+fn serve_fixtures() -> String {
+  let fixtures =
+    load_fixtures("examples/srl_stub_fixtures.json").expect("srl stub fixtures are valid");
+
+  let server = HttpServer::new(move || {
+    App::new()
+      .app_data(fixtures.clone())
+      .service(predict)
+  })
+  .workers(1)
+  .bind("127.0.0.1:0")
+  .expect("stub binds to a free port");
+
+  let addr = server.addrs()[0];
+  actix_web::rt::spawn(server.run());
+
+  format!("http://{}/predict", addr)
+}
+
+// The linkage of SENTENCE, as the link parser gives it.
+fn links() -> LPSentence {
+  let words: [(&str, &str); 10] = [
+    ("LEFT-WALL", "hWd+ hWV+ RW+"),
+    ("Jeff.m", "Wd- Ss*s+"),
+    ("bought.v-d", "S- dWV- O+ @MV+"),
+    ("ten", "Dmcn+"),
+    ("ice.n-u", "AN+"),
+    ("cream.s", "AN+"),
+    ("cones.n", "@AN- Dmc- Op- @M+"),
+    ("for.p", "Mp- MVp- J+"),
+    ("John.m", "Js-"),
+    ("RIGHT-WALL", "RW-"),
+  ];
+
+  let mut offset = 0;
+  let pairs = words
+    .iter()
+    .map(|(word, disjuncts)| {
+      let text = word
+        .split('.')
+        .next()
+        .unwrap();
+      let (start, end) = match SENTENCE[offset..].find(text) {
+        Some(i) if !word.ends_with("WALL") => (offset + i, offset + i + text.len()),
+        _ => (offset, offset),
+      };
+      offset = end;
+      let range = start as u64..=end as u64;
+
+      (
+        word.to_string(),
+        disjuncts
+          .split(' ')
+          .map(|d| d.to_string())
+          .collect(),
+        range.clone(),
+        range,
+      )
+    })
+    .collect();
+
+  LPSentence::new(SENTENCE, pairs)
+}
+
+fn parts(srl: SRLResponse) -> SentenceParts {
+  SentenceParts {
+    original_sentence: SENTENCE.to_string(),
+    corrected_sentence: SENTENCE.to_string(),
+    lemmatized_sentence: SENTENCE.to_string(),
+    tokens: vec![],
+    links: links(),
+    chunks: vec![],
+    duck: Duck::default(),
+    srl: Some(srl),
+  }
+}
+
+#[actix_web::test]
+async fn gets_labels_from_the_stub() {
+  let srl_url = serve_fixtures();
+
+  let srl = get_semantic_role_labels_from(&srl_url, SENTENCE)
+    .await
+    .unwrap();
+
+  assert_eq!(srl.verbs.len(), 1);
+  assert_eq!(srl.verbs[0].verb, "bought");
+  assert_eq!(srl.words.len(), 8);
+}
+
+#[actix_web::test]
+async fn cross_check_fills_in_and_flags_roles() {
+  let srl_url = serve_fixtures();
+
+  let srl = get_semantic_role_labels_from(&srl_url, SENTENCE)
+    .await
+    .unwrap();
+
+  // Jeff $1, bought $2, the cones $3 and John $4. The link parser's agent is left out, so srl
+  // has to fill it in, and nothing connects John, so srl's ARG4 is a disagreement.
+  let mut symbol = Symbol::new(4);
+  let mut parse_state = ParseState::new();
+  parse_state.add_symbol("$1", vec![1]);
+  parse_state.add_symbol("$2", vec![2]);
+  parse_state.add_symbol("$3", vec![4, 5, 6]);
+  parse_state.add_symbol("$4", vec![8]);
+
+  let mut sema_sentence = SemaSentence::new();
+  sema_sentence
+    .actions
+    .push(Action {
+      symbol: "$2".to_string(),
+      properties: vec![ActionProperties::Theme {
+        theme: "$3".to_string(),
+      }],
+      ..Action::from_lemma("buy")
+    });
+
+  let checked =
+    cross_check_srl(&sema_sentence, &parts(srl), &mut symbol, &mut parse_state).unwrap();
+
+  let action = &checked.actions[0];
+  assert!(action
+    .properties
+    .iter()
+    .any(|p| matches!(p, ActionProperties::Agent { agent } if agent == "$1")));
+
+  // ARG1 agrees with the link parser's theme, so it isn't reported.
+  assert_eq!(
+    checked
+      .diagnostics
+      .len(),
+    2
+  );
+  assert!(checked
+    .diagnostics
+    .iter()
+    .all(|d| d.stage == DiagnosticStages::Srl));
+  assert_eq!(checked.diagnostics[0].kind, DiagnosticKinds::Repaired);
+  assert_eq!(checked.diagnostics[0].symbols, vec!["$2", "$1"]);
+  assert_eq!(checked.diagnostics[1].kind, DiagnosticKinds::Disagreement);
+  assert_eq!(checked.diagnostics[1].symbols, vec!["$2", "$4"]);
+}
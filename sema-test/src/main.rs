@@ -10,12 +10,16 @@ use std::{env, fs::File, io::BufReader, panic};
 use link_parser_rust_bindings::{LinkParser, LinkParserOptions};
 
 use sema_api::{
+  adjectives::init_adjectives_cells,
+  frames::init_frames_cells,
   nlp::{
     init_nlp_cells,
     sentence_parts::{SentenceEncodings, SentenceParts, SentenceText},
   },
   process_sentences::process::process_parts,
+  propbank::init_propbank_cells,
   sema::sema_sentence::SemaSentence,
+  verbnet::init_verbnet_cells,
   wordnet::init_wordnet_cells,
 };
 
@@ -63,6 +67,10 @@ async fn main() -> Result<()> {
 
   init_nlp_cells(&data_path);
   init_wordnet_cells(&data_path);
+  init_verbnet_cells(&data_path);
+  init_propbank_cells(&data_path);
+  init_frames_cells(&data_path);
+  init_adjectives_cells(&data_path);
 
   // Link Parser (cannot be in a OnceCell!)
   let link_parser_ops = LinkParserOptions {
@@ -136,10 +144,10 @@ async fn _load_temporal_tests() -> Result<Vec<TestCase>> {
 fn create_parts(link_parser: &LinkParser, sentence: &str) -> Result<SentenceParts> {
   let sentence_text = SentenceText::new(sentence.to_owned(), SentenceEncodings::None, false)?;
   let mut parts = SentenceParts::from_text(&sentence_text)?; //.map_err(SemaAPiError::from)?;
-  let links = link_parser
-    .parse_sentence(&parts.corrected_sentence)?
-    .unwrap();
-  parts.links = links;
+  // Sentences without a linkage are left to the token parser.
+  if let Some(links) = link_parser.parse_sentence(&parts.corrected_sentence)? {
+    parts.links = links;
+  }
   Ok(parts)
 }
 